@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use aoc2021::{day14, day7, read_input, solutions, Variant};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_days(c: &mut Criterion) {
+    for (day, solution) in solutions() {
+        let input = match read_input(day, Variant::Real) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Skipping day {}: {}", day, e);
+                continue;
+            }
+        };
+        let mut group = c.benchmark_group(format!("day{}", day));
+        group.bench_function("parse", |b| {
+            b.iter(|| solution.parse(black_box(&input)).unwrap())
+        });
+        for &part in solution.parts() {
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| solution.solve(part, black_box(&input)).unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_day7_fuel(c: &mut Criterion) {
+    let positions = day7::parse(read_input(7, Variant::Real).unwrap().trim()).unwrap();
+    let mut group = c.benchmark_group("day7_fuel");
+    group.bench_function("mean", |b| {
+        b.iter(|| day7::calc_fuel_for_position2(black_box(positions.clone())))
+    });
+    group.bench_function("exhaustive", |b| {
+        b.iter(|| day7::calc_fuel_for_position2_exhaustive(black_box(positions.clone())))
+    });
+    group.finish();
+}
+
+fn bench_day14_rules(c: &mut Criterion) {
+    let input = read_input(14, Variant::Real).unwrap();
+    let (init, rules, unique) = day14::parse(input.as_bytes()).unwrap();
+    let rules_map: HashMap<_, _> = rules.iter().copied().collect();
+    let mut group = c.benchmark_group("day14_10_steps");
+    group.bench_function("apply_rules", |b| {
+        b.iter(|| {
+            let mut list = init.clone();
+            for _ in 0..10 {
+                day14::apply_rules(&mut list, &rules_map);
+            }
+            day14::get_min_max_occurance(list)
+        })
+    });
+    group.bench_function("apply_n_rules_fast", |b| {
+        b.iter(|| day14::apply_n_rules_fast(init.clone(), rules.clone(), 10, unique))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_days, bench_day7_fuel, bench_day14_rules);
+criterion_main!(benches);
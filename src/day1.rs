@@ -1,6 +1,17 @@
-use itertools::{process_results, Itertools};
-use simple_lines::ReadExt;
-use std::fs::File;
+use itertools::Itertools;
+use std::{collections::VecDeque, fs::File, io::Read, path::Path};
+
+use crate::{
+    error::{ErrorPolicy, ParseError, Parsed},
+    utils::{parse_lines, parse_lines_with},
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 1;
+
+fn parse(r: impl Read) -> impl Iterator<Item = Result<usize, ParseError>> {
+    parse_lines(DAY, r, |line| Ok(line.parse()?))
+}
 
 pub fn count_increments(input: impl IntoIterator<Item = usize>) -> usize {
     input
@@ -10,66 +21,267 @@ pub fn count_increments(input: impl IntoIterator<Item = usize>) -> usize {
         .count()
 }
 
-pub fn maybe_panic_count_increments_in_file(path: &str) -> usize {
-    let file = std::fs::File::open(path).expect("File always exists");
-    let numbers = file.lines_rc().map(|line| {
-        line.expect("no invalid string")
-            .parse()
-            .expect("line is a number")
-    });
-    count_increments(numbers)
+/// Counts the increments of the depths in the file, where the policy decides about invalid lines
+pub fn count_increments_in_file(
+    path: impl AsRef<Path>,
+    policy: ErrorPolicy,
+) -> Result<Parsed<usize>, Box<dyn std::error::Error>> {
+    #[allow(clippy::redundant_closure)]
+    read_depths_in_file(path, policy, |depths| count_increments(depths))
+}
+
+/// Reads the depths of the file line by line, which only fails at invalid lines with
+/// `ErrorPolicy::FailFast` or if the file can't be opened
+fn read_depths_in_file<R>(
+    path: impl AsRef<Path>,
+    policy: ErrorPolicy,
+    consume: impl FnOnce(&mut dyn Iterator<Item = usize>) -> R,
+) -> Result<Parsed<R>, Box<dyn std::error::Error>> {
+    Ok(read_depths(File::open(path)?, policy, consume)?)
+}
+
+fn read_depths<R>(
+    r: impl Read,
+    policy: ErrorPolicy,
+    consume: impl FnOnce(&mut dyn Iterator<Item = usize>) -> R,
+) -> Result<Parsed<R>, ParseError> {
+    parse_lines_with(DAY, r, policy, |line| Ok(line.parse()?), consume)
+}
+
+pub fn count_window_increments(input: impl IntoIterator<Item = usize>) -> usize {
+    count_sliding_increments(input, 3)
+}
+
+/// Counts increases of the sums over sliding windows of several sizes at once
+///
+/// Two neighbouring windows of size N share all but their first and last value, so the sum
+/// increases exactly if the value entering the window is larger than the one leaving it.
+/// Only the last N values of the largest window are kept and nothing is ever summed up.
+#[derive(Debug, Clone)]
+pub struct WindowIncrements<T> {
+    recent: VecDeque<T>,
+    largest: usize,
+    sizes: Vec<usize>,
+    counts: Vec<usize>,
+}
+
+impl<T: PartialOrd> WindowIncrements<T> {
+    /// Windows of size 0 never increase
+    pub fn new(sizes: &[usize]) -> Self {
+        let largest = sizes.iter().copied().max().unwrap_or(0);
+        Self {
+            recent: VecDeque::with_capacity(largest + 1),
+            largest,
+            sizes: sizes.to_vec(),
+            counts: vec![0; sizes.len()],
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        for (size, count) in self.sizes.iter().zip(&mut self.counts) {
+            if (1..=self.recent.len()).contains(size)
+                && self.recent[self.recent.len() - size] < value
+            {
+                *count += 1;
+            }
+        }
+        self.recent.push_back(value);
+        if self.recent.len() > self.largest {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Number of increases for each size in the order given to `new`
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
 }
 
-// No collect ignore errors
-pub fn sloppy_count_increments_in_file(path: &str) -> usize {
-    if let Ok(file) = std::fs::File::open(path) {
-        let numbers = file
-            .lines_rc()
-            //.filter_map(Result::ok)
-            .filter_map(|e| e.ok())
-            .filter_map(|line| line.parse().ok());
-        count_increments(numbers)
-    } else {
-        0
+impl<T: PartialOrd> Extend<T> for WindowIncrements<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push(value));
     }
 }
 
-pub fn count_increments_in_file_collect(path: &str) -> Result<usize, Box<dyn std::error::Error>> {
-    let file = std::fs::File::open(path)?;
-    let numbers = file
-        .lines_rc()
-        .map(|line| Ok(line?.parse()?))
-        .collect::<Result<Vec<usize>, Box<dyn std::error::Error>>>()?;
-    Ok(count_increments(numbers))
+/// Counts how often the sum over a sliding window of `size` values increases
+pub fn count_sliding_increments<T: PartialOrd>(
+    input: impl IntoIterator<Item = T>,
+    size: usize,
+) -> usize {
+    count_sliding_increments_by(input, &[size])[0]
 }
 
-pub fn count_increments_in_file(path: &str) -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open(path)?;
-    let numbers = file.lines_rc().map(|line| Ok(line?.parse()?));
+/// Counts the increases for each of the window `sizes` in a single pass
+pub fn count_sliding_increments_by<T: PartialOrd>(
+    input: impl IntoIterator<Item = T>,
+    sizes: &[usize],
+) -> Vec<usize> {
+    let mut increments = WindowIncrements::new(sizes);
+    increments.extend(input);
+    increments.counts
+}
+
+pub fn count_window_increments_in_file(
+    path: impl AsRef<Path>,
+    policy: ErrorPolicy,
+) -> Result<Parsed<usize>, Box<dyn std::error::Error>> {
     #[allow(clippy::redundant_closure)]
-    process_results(numbers, |iter| count_increments(iter))
+    read_depths_in_file(path, policy, |depths| count_window_increments(depths))
 }
 
-pub fn count_window_increments(input: impl IntoIterator<Item = usize>) -> usize {
-    let sums = input.into_iter().tuple_windows().map(|(a, b, c)| a + b + c);
-    count_increments(sums)
+/// Readings from `start` to `end`, both as 1-based line numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
 }
 
-pub fn count_window_increments_in_file(path: &str) -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open(path)?;
-    let numbers = file.lines_rc().map(|line| Ok(line?.parse()?));
-    #[allow(clippy::redundant_closure)]
-    process_results(numbers, |iter| count_window_increments(iter))
+impl Run {
+    /// Number of readings within the run
+    pub fn readings(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+/// Change of depth between the reading at `line` and the one before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    pub line: usize,
+    pub change: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepthStats {
+    pub readings: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+    /// Longest strictly increasing run, the first one if several are equally long
+    pub longest_run: Option<Run>,
+    /// Largest change in either direction, the first one if several are equally large
+    pub largest_jump: Option<Jump>,
+}
+
+/// Analyzes depth readings one at a time, so the statistics are up to date after every reading
+#[derive(Debug, Clone, Default)]
+pub struct DepthAnalyzer {
+    previous: Option<usize>,
+    run_start: usize,
+    stats: DepthStats,
+}
+
+impl DepthAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, depth: usize) {
+        let stats = &mut self.stats;
+        stats.readings += 1;
+        let line = stats.readings;
+        match self.previous {
+            Some(previous) if previous < depth => stats.increases += 1,
+            Some(previous) if previous > depth => stats.decreases += 1,
+            Some(_) => stats.plateaus += 1,
+            None => {}
+        }
+        if let Some(previous) = self.previous {
+            if previous >= depth {
+                self.run_start = line;
+            }
+            let change = depth as i64 - previous as i64;
+            if stats
+                .largest_jump
+                .is_none_or(|jump| jump.change.abs() < change.abs())
+            {
+                stats.largest_jump = Some(Jump { line, change });
+            }
+        } else {
+            self.run_start = line;
+        }
+        let run = Run {
+            start: self.run_start,
+            end: line,
+        };
+        if stats
+            .longest_run
+            .is_none_or(|longest| longest.readings() < run.readings())
+        {
+            stats.longest_run = Some(run);
+        }
+        self.previous = Some(depth);
+    }
+
+    pub fn stats(&self) -> &DepthStats {
+        &self.stats
+    }
+}
+
+impl Extend<usize> for DepthAnalyzer {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|depth| self.push(depth));
+    }
+}
+
+/// Analyzes all readings, e.g. of a `std::sync::mpsc::Receiver` until every sender is gone
+pub fn analyze_depths(readings: impl IntoIterator<Item = usize>) -> DepthStats {
+    let mut analyzer = DepthAnalyzer::new();
+    analyzer.extend(readings);
+    analyzer.stats
+}
+
+/// Analyzes the reader line by line and stops at the first invalid reading
+pub fn analyze_depths_in_reader(r: impl Read) -> Result<DepthStats, ParseError> {
+    let mut analyzer = DepthAnalyzer::new();
+    for depth in parse(r) {
+        analyzer.push(depth?);
+    }
+    Ok(analyzer.stats)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        #[allow(clippy::redundant_closure)]
+        let count = read_depths(input.as_bytes(), ErrorPolicy::FailFast, |depths| {
+            count_increments(depths)
+        })?;
+        Ok(Box::new(count.result))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        #[allow(clippy::redundant_closure)]
+        let count = read_depths(input.as_bytes(), ErrorPolicy::FailFast, |depths| {
+            count_window_increments(depths)
+        })?;
+        Ok(Box::new(count.result))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        error::ErrorPolicy,
+        inputs::{InputStore, Variant},
+    };
+
     const TEST_INPUT: [usize; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
     #[test]
     fn input_part1() {
         assert_eq!(
             1688,
-            super::count_increments_in_file("puzzleData/day1.txt").unwrap()
+            super::count_increments_in_file(
+                InputStore::from_env().path(1, Variant::Real),
+                ErrorPolicy::FailFast
+            )
+            .unwrap()
+            .result
         );
     }
     #[test]
@@ -77,16 +289,92 @@ mod tests {
         assert_eq!(7, super::count_increments(TEST_INPUT));
     }
 
+    #[test]
+    fn locate_invalid_number() {
+        let error = super::parse(&b"199\n200\n2x8"[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((1, 3, 1), (error.day, error.line, error.column));
+    }
+
     #[test]
     fn test_input_part2() {
         assert_eq!(5, super::count_window_increments(TEST_INPUT))
     }
 
+    #[test]
+    fn sliding_windows() {
+        let naive = |size: usize| {
+            let sums = TEST_INPUT
+                .windows(size)
+                .map(|w| w.iter().sum())
+                .collect::<Vec<usize>>();
+            super::count_increments(sums)
+        };
+        let sizes = [1, 2, 3, 4, 10, 11];
+        assert_eq!(
+            sizes.map(naive).to_vec(),
+            super::count_sliding_increments_by(TEST_INPUT, &sizes)
+        );
+        assert_eq!(0, super::count_sliding_increments(TEST_INPUT, 0));
+        let floats = TEST_INPUT.map(|depth| depth as f64 / 2.0);
+        assert_eq!(5, super::count_sliding_increments(floats, 3));
+        let signed = TEST_INPUT.map(|depth| -(depth as i64));
+        assert_eq!(1, super::count_sliding_increments(signed, 3));
+    }
+
+    #[test]
+    fn depth_stats() {
+        let stats = super::analyze_depths_in_reader(
+            &b"199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n263"[..],
+        )
+        .unwrap();
+        assert_eq!(
+            super::DepthStats {
+                readings: 11,
+                increases: 7,
+                decreases: 2,
+                plateaus: 1,
+                longest_run: Some(super::Run { start: 1, end: 4 }),
+                largest_jump: Some(super::Jump {
+                    line: 7,
+                    change: 33
+                }),
+            },
+            stats
+        );
+        assert_eq!(super::DepthStats::default(), super::analyze_depths([]));
+        assert!(super::analyze_depths_in_reader(&b"1\nx"[..]).is_err());
+    }
+
+    #[test]
+    fn depths_from_channel() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sensor = std::thread::spawn(move || {
+            for depth in [5, 3, 4, 4, 10] {
+                sender.send(depth).unwrap();
+            }
+        });
+        let stats = super::analyze_depths(receiver);
+        sensor.join().unwrap();
+        assert_eq!(
+            (2, 1, 1),
+            (stats.increases, stats.decreases, stats.plateaus)
+        );
+        assert_eq!(Some(super::Run { start: 2, end: 3 }), stats.longest_run);
+        assert_eq!(Some(super::Jump { line: 5, change: 6 }), stats.largest_jump);
+    }
+
     #[test]
     fn input_part2() {
         assert_eq!(
             1728,
-            super::count_window_increments_in_file("puzzleData/day1.txt").unwrap()
+            super::count_window_increments_in_file(
+                InputStore::from_env().path(1, Variant::Real),
+                ErrorPolicy::Skip
+            )
+            .unwrap()
+            .result
         )
     }
 }
@@ -1,11 +1,20 @@
 use std::io::Read;
 
 use itertools::Itertools;
-use simple_lines::ReadExt;
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 10;
 
 fn parse_line(input: &str) -> Result<(), Error> {
     let mut buf = vec![];
-    for char in input.chars() {
+    for (column, char) in input.chars().enumerate() {
+        let invalid_char =
+            |c| Error::InvalidChar(LineError::new(column + 1, ParseErrorKind::InvalidChar(c)));
         match char {
             '<' => buf.push('>'),
             '(' => buf.push(')'),
@@ -17,9 +26,9 @@ fn parse_line(input: &str) -> Result<(), Error> {
                         return Err(Error::CorruptLine(i));
                     }
                 }
-                None => return Err(Error::InvalidChar(i)),
+                None => return Err(invalid_char(i)),
             },
-            a => return Err(Error::InvalidChar(a)),
+            a => return Err(invalid_char(a)),
         }
     }
     if !buf.is_empty() {
@@ -29,92 +38,119 @@ fn parse_line(input: &str) -> Result<(), Error> {
     Ok(())
 }
 
-fn get_error_score_corrupt(r: impl Read) -> i32 {
-    r.lines_rc()
-        .filter_map(|line| {
-            if let Err(Error::CorruptLine(i)) = parse_line(&line.unwrap()) {
-                Some(match i {
-                    ')' => 3,
-                    ']' => 57,
-                    '}' => 1197,
-                    _ => 25137,
-                })
-            } else {
-                None
-            }
-        })
-        .sum::<i32>()
+fn get_error_score_corrupt(r: impl Read) -> Result<i32, ParseError> {
+    parse_lines(DAY, r, |line| match parse_line(line) {
+        Err(Error::CorruptLine(i)) => Ok(match i {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            _ => 25137,
+        }),
+        Err(Error::InvalidChar(e)) => Err(e),
+        _ => Ok(0),
+    })
+    .sum::<Result<i32, _>>()
 }
 
-fn get_error_score_incomplete(r: impl Read) -> u64 {
-    let mut sorted = r
-        .lines_rc()
-        .filter_map(|line| {
-            if let Err(Error::Incomplete(i)) = parse_line(&line.unwrap()) {
-                Some(
-                    i.iter()
-                        .rev()
-                        .map(|x| match x {
-                            ')' => 1,
-                            ']' => 2,
-                            '}' => 3,
-                            _ => 4,
-                        })
-                        .fold(0, |acc, n| acc * 5 + n),
-                )
-            } else {
-                None
-            }
-        })
-        .collect_vec();
+fn get_error_score_incomplete(r: impl Read) -> Result<u64, ParseError> {
+    let mut sorted = parse_lines(DAY, r, |line| match parse_line(line) {
+        Err(Error::Incomplete(i)) => Ok(Some(
+            i.iter()
+                .rev()
+                .map(|x| match x {
+                    ')' => 1,
+                    ']' => 2,
+                    '}' => 3,
+                    _ => 4,
+                })
+                .fold(0, |acc, n| acc * 5 + n),
+        )),
+        Err(Error::InvalidChar(e)) => Err(e),
+        _ => Ok(None),
+    })
+    .flatten_ok()
+    .collect::<Result<Vec<_>, _>>()?;
     sorted.sort_unstable();
 
-    sorted[sorted.len() / 2]
+    sorted
+        .get(sorted.len() / 2)
+        .copied()
+        .ok_or_else(|| LineError::new(1, ParseErrorKind::EmptyInput).locate(DAY, 1, ""))
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error("invalid point format")]
-    InvalidChar(char),
-
-    #[error("Line is corrupt")]
+#[derive(Debug)]
+enum Error {
+    InvalidChar(LineError),
     CorruptLine(char),
-
-    #[error("Remainings")]
     Incomplete(Vec<char>),
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse_lines(DAY, input.as_bytes(), |line| match parse_line(line) {
+            Err(Error::InvalidChar(e)) => Err(e),
+            _ => Ok(()),
+        })
+        .collect::<Result<(), _>>()?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(get_error_score_corrupt(input.as_bytes())?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(get_error_score_incomplete(input.as_bytes())?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
-        let result = get_error_score_corrupt(std::fs::File::open("puzzleData/day10.txt").unwrap());
+        let result =
+            get_error_score_corrupt(read_input(10, Variant::Real).unwrap().as_bytes()).unwrap();
         assert_eq!(370407, result);
     }
 
     #[test]
     fn part1_test() {
-        let result = get_error_score_corrupt(std::io::Cursor::new(TEST_INPUT));
+        let result = get_error_score_corrupt(std::io::Cursor::new(TEST_INPUT)).unwrap();
         assert_eq!(26397, result);
     }
 
     #[test]
     fn part2() {
         let result =
-            get_error_score_incomplete(std::fs::File::open("puzzleData/day10.txt").unwrap());
+            get_error_score_incomplete(read_input(10, Variant::Real).unwrap().as_bytes()).unwrap();
         assert_eq!(3249889609, result);
     }
 
     #[test]
     fn part2_test() {
-        let result = get_error_score_incomplete(std::io::Cursor::new(TEST_INPUT));
+        let result = get_error_score_incomplete(std::io::Cursor::new(TEST_INPUT)).unwrap();
         assert_eq!(288957, result);
     }
 
-    const TEST_INPUT: &'static str = "[({(<(())[]>[[{[]{<()<>>
+    #[test]
+    fn locate_invalid_char() {
+        let result = get_error_score_corrupt(std::io::Cursor::new("[()]\n[(x)]"));
+        let error = result.unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
+
+    #[test]
+    fn parse_only() {
+        assert!(Day10.parse(TEST_INPUT).is_ok());
+        assert!(Day10.parse("[(x)]").is_err());
+    }
+
+    const TEST_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
 [(()[<>])]({[<{<<[]>>(
 {([(<{}[<>[]}>{[]{[(<()>
 (((({<>}<{<{<>}{[]{[]{}
@@ -1,12 +1,16 @@
-use itertools::{iproduct, Itertools};
-use pathfinding::prelude::{bfs_reach, Matrix};
+use itertools::Itertools;
+use pathfinding::prelude::bfs_reach;
 
-fn apply_neighbours(m: &mut Matrix<u8>) -> u64 {
-    for k in iproduct!(0..m.rows, 0..m.columns) {
+use crate::{error::ParseError, utils::first_step, Answer, DynError, Grid, Solution};
+
+const DAY: u8 = 11;
+
+fn apply_neighbours(m: &mut Grid<u8>) -> u64 {
+    for k in m.positions() {
         m[k] += 1;
         if m[k] == 10 {
             bfs_reach(k, |&n| {
-                m.neighbours(n, true)
+                m.neighbours8(n)
                     .filter(|&k| {
                         m[k] += 1;
                         m[k] == 10
@@ -28,42 +32,59 @@ fn apply_neighbours(m: &mut Matrix<u8>) -> u64 {
         .sum()
 }
 
-fn count_flashes_for_100_steps(input: &str) -> u64 {
-    let mut m: Matrix<u8> = input.lines().map(|c| c.bytes().map(|x| x - b'0')).collect();
-    (0..100).map(|_| apply_neighbours(&mut m)).sum::<u64>()
+fn count_flashes_for_100_steps(input: &str) -> Result<u64, ParseError> {
+    let mut m = Grid::parse_digits(DAY, input)?;
+    Ok((0..100).map(|_| apply_neighbours(&mut m)).sum::<u64>())
 }
 
-fn detect_first_flash_of_all_cylce(input: &str) -> u64 {
-    let mut m: Matrix<u8> = input.lines().map(|c| c.bytes().map(|x| x - b'0')).collect();
-    (1..).find(|_| apply_neighbours(&mut m) == 100).unwrap() as u64
+fn detect_first_flash_of_all_cylce(input: &str) -> Result<u64, ParseError> {
+    let mut m = Grid::parse_digits(DAY, input)?;
+    Ok(first_step(|| apply_neighbours(&mut m) == 100) as u64)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        Grid::parse_digits(DAY, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_flashes_for_100_steps(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(detect_first_flash_of_all_cylce(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
-        let buf = std::fs::read_to_string("puzzleData/day11.txt").unwrap();
-        assert_eq!(1721, count_flashes_for_100_steps(&buf));
+        let buf = read_input(11, Variant::Real).unwrap();
+        assert_eq!(1721, count_flashes_for_100_steps(&buf).unwrap());
     }
     #[test]
     fn part1_test() {
-        assert_eq!(1656, count_flashes_for_100_steps(TESTDATA));
+        assert_eq!(1656, count_flashes_for_100_steps(TESTDATA).unwrap());
     }
 
     #[test]
     fn part2() {
-        let buf = std::fs::read_to_string("puzzleData/day11.txt").unwrap();
-        assert_eq!(298, detect_first_flash_of_all_cylce(&buf));
+        let buf = read_input(11, Variant::Real).unwrap();
+        assert_eq!(298, detect_first_flash_of_all_cylce(&buf).unwrap());
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(195, detect_first_flash_of_all_cylce(TESTDATA));
+        assert_eq!(195, detect_first_flash_of_all_cylce(TESTDATA).unwrap());
     }
-    const TESTDATA: &'static str = "5483143223
+    const TESTDATA: &str = "5483143223
 2745854711
 5264556173
 6141336146
@@ -1,5 +1,3 @@
-use itertools::process_results;
-use simple_lines::ReadExt;
 use std::{
     borrow::Borrow,
     collections::{HashMap, HashSet},
@@ -7,19 +5,31 @@ use std::{
     rc::Rc,
 };
 
-use crate::utils::AocError;
+use crate::{
+    error::{ErrorPolicy, LineError, ParseError, ParseErrorKind},
+    utils::parse_lines_with,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 12;
 
 type CaveMap = HashMap<Rc<str>, CaveReferences>;
 
-fn parse(m: impl Read) -> Result<CaveMap, Box<dyn std::error::Error>> {
-    process_results(
-        m.lines_rc().map(|f| {
-            let f = f?;
+fn parse(m: impl Read) -> Result<CaveMap, ParseError> {
+    let parsed = parse_lines_with(
+        DAY,
+        m,
+        ErrorPolicy::FailFast,
+        |f| {
             let mut parts = f.split('-');
-            let a: Cave = parts.next().ok_or(AocError::InvalidRowFormat)?.try_into()?;
-            let b: Cave = parts.next().ok_or(AocError::InvalidRowFormat)?.try_into()?;
-            Ok((a, b))
-        }),
+            let mut next_cave = || {
+                let part = parts
+                    .next()
+                    .ok_or_else(|| LineError::after(f, ParseErrorKind::Missing("cave")))?;
+                Cave::try_from(part).map_err(|kind| LineError::at(f, part, kind))
+            };
+            Ok((next_cave()?, next_cave()?))
+        },
         |iter| {
             let mut result: HashMap<Rc<str>, CaveReferences> = HashMap::new();
             for (a, b) in iter {
@@ -40,23 +50,25 @@ fn parse(m: impl Read) -> Result<CaveMap, Box<dyn std::error::Error>> {
             }
             result
         },
-    )
+    )?;
+    Ok(parsed.result)
 }
 
 impl<'a> TryFrom<&'a str> for Cave {
-    type Error = AocError;
+    type Error = ParseErrorKind;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         if let Some(first_char) = value.chars().next() {
             Ok(Cave {
                 id: Rc::from(value),
-                t: first_char
-                    .is_uppercase()
-                    .then(|| CaveType::Big)
-                    .unwrap_or(CaveType::Small),
+                t: if first_char.is_uppercase() {
+                    CaveType::Big
+                } else {
+                    CaveType::Small
+                },
             })
         } else {
-            Err(AocError::InvalidRowFormat)
+            Err(ParseErrorKind::Missing("cave name"))
         }
     }
 }
@@ -112,13 +124,31 @@ fn count_paths(map: CaveMap, allow_single_double_visit: bool) -> u64 {
     solutions
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_paths(parse(input.as_bytes())?, false)))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_paths(parse(input.as_bytes())?, true)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
-        let map = parse(std::fs::File::open("puzzleData/day12.txt").unwrap()).unwrap();
+        let map = parse(read_input(12, Variant::Real).unwrap().as_bytes()).unwrap();
         assert_eq!(4186, count_paths(map, false));
     }
     #[test]
@@ -129,7 +159,7 @@ mod tests {
 
     #[test]
     fn part2_real() {
-        let map = parse(std::fs::File::open("puzzleData/day12.txt").unwrap()).unwrap();
+        let map = parse(read_input(12, Variant::Real).unwrap().as_bytes()).unwrap();
         assert_eq!(92111, count_paths(map, true));
     }
 
@@ -139,7 +169,13 @@ mod tests {
         assert_eq!(103, count_paths(map, true));
     }
 
-    const TESTDATA: &'static str = "dc-end
+    #[test]
+    fn locate_missing_cave() {
+        let error = parse(std::io::Cursor::new("start-A\nA-")).unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
+
+    const TESTDATA: &str = "dc-end
 HN-start
 start-kj
 dc-start
@@ -1,46 +1,79 @@
-use std::io::Read;
+use std::{collections::HashSet, io::Read};
 
-use simple_lines::ReadExt;
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::numbered_lines,
+    Answer, DynError, Grid, Solution,
+};
 
-use crate::utils::AocError;
+const DAY: u8 = 13;
 
 type ParseOk = (Vec<(i64, i64)>, Vec<FoldInstruction>);
-fn parse(r: impl Read) -> Result<ParseOk, Box<dyn std::error::Error>> {
-    let mut lines = r.lines_rc();
-    let map: Result<Vec<_>, Box<dyn std::error::Error>> = lines
+fn parse(r: impl Read) -> Result<ParseOk, ParseError> {
+    let mut lines = numbered_lines(DAY, r);
+    let map: Result<Vec<_>, ParseError> = lines
         .by_ref()
         .take_while(|x| match x {
-            Ok(ok) => !ok.is_empty(),
+            Ok((_, ok)) => !ok.is_empty(),
             Err(_) => true,
         })
         .map(|line| {
-            let line = line?;
-            let mut parts = line.split(',');
-            let x = parts.next().ok_or(AocError::InvalidRowFormat)?.parse()?;
-            let y = parts.next().ok_or(AocError::InvalidRowFormat)?.parse()?;
-            Ok((x, y))
+            let (no, line) = line?;
+            parse_dot(&line).map_err(|e| e.locate(DAY, no, &line))
         })
         .collect();
     let map = map?;
 
-    let instructions: Result<Vec<_>, Box<dyn std::error::Error>> = lines
+    let instructions: Result<Vec<_>, ParseError> = lines
         .map(|line| {
-            let line = line?;
-            let chars = &line["fold along ".len()..];
-            let instruction = chars.chars().next().ok_or(AocError::InvalidRowFormat)?;
-            let nr: i64 = chars[2..].parse()?;
-            Ok(match instruction {
-                'x' => FoldInstruction::X(nr),
-                'y' => FoldInstruction::Y(nr),
-                _ => return Err(AocError::InvalidRowFormat.into()),
-            })
+            let (no, line) = line?;
+            parse_instruction(&line).map_err(|e| e.locate(DAY, no, &line))
         })
         .collect();
 
     Ok((map, instructions?))
 }
 
-fn apply_instructions(map: &mut Vec<(i64, i64)>, instructions: &[FoldInstruction]) {
+fn parse_dot(line: &str) -> Result<(i64, i64), LineError> {
+    let mut parts = line.split(',');
+    let mut next_coordinate = |name| {
+        let part = parts
+            .next()
+            .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing(name)))?;
+        let coordinate: i64 = part.parse().map_err(|e| LineError::at(line, part, e))?;
+        if coordinate < 0 {
+            let kind = ParseErrorKind::Invalid("negative coordinate");
+            return Err(LineError::at(line, part, kind));
+        }
+        Ok(coordinate)
+    };
+    Ok((next_coordinate("x")?, next_coordinate("y")?))
+}
+
+fn parse_instruction(line: &str) -> Result<FoldInstruction, LineError> {
+    let chars = line
+        .strip_prefix("fold along ")
+        .ok_or_else(|| LineError::new(1, ParseErrorKind::UnknownCommand(line.to_owned())))?;
+    let (instruction, nr) = chars
+        .split_once('=')
+        .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing("'='")))?;
+    let value: i64 = nr.parse().map_err(|e| LineError::at(line, nr, e))?;
+    if value < 0 {
+        let kind = ParseErrorKind::Invalid("negative fold line");
+        return Err(LineError::at(line, nr, kind));
+    }
+    match instruction {
+        "x" => Ok(FoldInstruction::X(value)),
+        "y" => Ok(FoldInstruction::Y(value)),
+        _ => Err(LineError::at(
+            line,
+            instruction,
+            ParseErrorKind::InvalidRowFormat,
+        )),
+    }
+}
+
+fn apply_instructions(map: &mut [(i64, i64)], instructions: &[FoldInstruction]) {
     for instr in instructions {
         map.iter_mut().for_each(|(x, y)| match instr {
             FoldInstruction::X(x_fold) => {
@@ -57,20 +90,52 @@ fn apply_instructions(map: &mut Vec<(i64, i64)>, instructions: &[FoldInstruction
     }
 }
 
+fn render(map: &[(i64, i64)]) -> String {
+    let (max_x, max_y) = map.iter().fold((0, 0), |(acc_x, acc_y), (x, y)| {
+        (acc_x.max(*x as usize), acc_y.max(*y as usize))
+    });
+    let mut canvas = Grid::new(max_x + 1, max_y + 1, '.');
+    for (x, y) in map {
+        canvas[(*x as usize, *y as usize)] = '#';
+    }
+    canvas.to_string()
+}
+
 enum FoldInstruction {
     X(i64),
     Y(i64),
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let (mut map, instructions) = parse(input.as_bytes())?;
+        apply_instructions(&mut map, instructions.get(..1).unwrap_or(&[]));
+        Ok(Box::new(map.into_iter().collect::<HashSet<_>>().len()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let (mut map, instructions) = parse(input.as_bytes())?;
+        apply_instructions(&mut map, &instructions);
+        Ok(Box::new(render(&map)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
         let (mut map, instructions) =
-            parse(std::fs::File::open("puzzleData/day13.txt").unwrap()).unwrap();
+            parse(read_input(13, Variant::Real).unwrap().as_bytes()).unwrap();
         apply_instructions(&mut map, &instructions[0..1]);
         assert_eq!(706, map.into_iter().collect::<HashSet<_>>().len() as i64);
     }
@@ -85,24 +150,30 @@ mod tests {
     #[test]
     fn part2() {
         let (mut map, instructions) =
-            parse(std::fs::File::open("puzzleData/day13.txt").unwrap()).unwrap();
+            parse(read_input(13, Variant::Real).unwrap().as_bytes()).unwrap();
         apply_instructions(&mut map, &instructions);
-        let (max_x, max_y) = map.iter().fold((0, 0), |(acc_x, acc_y), (x, y)| {
-            (acc_x.max(*x as usize), acc_y.max(*y as usize))
-        });
-        let (width, height) = (max_x + 2, max_y + 1);
-        let mut coords = vec![b'.'; width * height];
-        for (x, y) in map {
-            coords[x as usize + y as usize * width] = b'#';
-        }
-        for y in 1..height {
-            coords[y * width - 1] = b'\n';
-        }
-        let out = std::str::from_utf8(coords.as_slice()).unwrap();
-        assert_eq!("#....###..####...##.###....##.####.#..#\n#....#..#.#.......#.#..#....#.#....#..#\n#....#..#.###.....#.###.....#.###..####\n#....###..#.......#.#..#....#.#....#..#\n#....#.#..#....#..#.#..#.#..#.#....#..#\n####.#..#.#.....##..###...##..####.#..#.", out);
-        // println!("{}", std::str::from_utf8(coords.as_slice()).unwrap());
+        let out = render(&map);
+        assert_eq!("#....###..####...##.###....##.####.#..#\n#....#..#.#.......#.#..#....#.#....#..#\n#....#..#.###.....#.###.....#.###..####\n#....###..#.......#.#..#....#.#....#..#\n#....#.#..#....#..#.#..#.#..#.#....#..#\n####.#..#.#.....##..###...##..####.#..#", out);
+    }
+    #[test]
+    fn locate_invalid_instruction() {
+        let input = TEST_INPUT.replace("fold along x=5", "fold along z=5");
+        let error = parse(std::io::Cursor::new(input)).err().unwrap();
+        assert_eq!((21, 12), (error.line, error.column));
     }
-    const TEST_INPUT: &'static str = "6,10
+
+    #[test]
+    fn locate_negative_dot() {
+        let input = TEST_INPUT.replace("0,14", "0,-14");
+        let error = parse(std::io::Cursor::new(input)).err().unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Invalid(_)));
+        let input = TEST_INPUT.replace("fold along x=5", "fold along x=-5");
+        let error = parse(std::io::Cursor::new(input)).err().unwrap();
+        assert_eq!((21, 14), (error.line, error.column));
+    }
+
+    const TEST_INPUT: &str = "6,10
 0,14
 9,10
 0,3
@@ -1,16 +1,29 @@
 use itertools::{Itertools, MinMaxResult};
-use simple_lines::ReadExt;
 use std::{collections::HashMap, io::Read};
 
-use crate::{utils::AocError, DynError};
-type ParseResult = Result<(Vec<ListItem>, Vec<((u8, u8), u8)>, u8), DynError>;
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::numbered_lines,
+    Answer, DynError, Solution,
+};
 
-fn parse(input: impl Read) -> ParseResult {
+const DAY: u8 = 14;
+
+pub type ParseResult = Result<(Vec<ListItem>, Vec<((u8, u8), u8)>, u8), ParseError>;
+
+fn empty_input() -> ParseError {
+    LineError::new(1, ParseErrorKind::EmptyInput).locate(DAY, 1, "")
+}
+
+pub fn parse(input: impl Read) -> ParseResult {
     let mut known = HashMap::new();
-    let mut lines = input.lines_rc();
-    let initial = lines
-        .next()
-        .ok_or(AocError::EmptyInput)??
+    let mut lines = numbered_lines(DAY, input);
+    let (no, template) = lines.next().ok_or_else(empty_input)??;
+    if template.is_empty() {
+        let kind = ParseErrorKind::Missing("polymer template");
+        return Err(LineError::new(1, kind).locate(DAY, no, &template));
+    }
+    let initial = template
         .bytes()
         .enumerate()
         .map(|(index, value)| {
@@ -26,11 +39,9 @@ fn parse(input: impl Read) -> ParseResult {
     let rules = lines
         .skip(1)
         .map(|line| {
-            let line = line?;
-            let mut chars = line.bytes();
-            let before = chars.next().ok_or(AocError::InvalidRowFormat)?;
-            let after = chars.next().ok_or(AocError::InvalidRowFormat)?;
-            let insert = chars.nth(4).ok_or(AocError::InvalidRowFormat)?;
+            let (no, line) = line?;
+            let (before, after, insert) =
+                parse_rule(&line).map_err(|e| e.locate(DAY, no, &line))?;
 
             let len = known.len() as u8;
             let before = *known.entry(before).or_insert(len);
@@ -41,12 +52,35 @@ fn parse(input: impl Read) -> ParseResult {
 
             Ok(((before, after), insert))
         })
-        .collect::<Result<Vec<_>, DynError>>()?;
+        .collect::<Result<Vec<_>, ParseError>>()?;
     Ok((initial, rules, known.len() as u8))
 }
+
+/// Parses a rule like `CH -> B` into its raw bytes (before, after, insert)
+fn parse_rule(line: &str) -> Result<(u8, u8, u8), LineError> {
+    const ARROW: &str = " -> ";
+    let chars = line.chars().collect::<Vec<_>>();
+    if chars.len() != 3 + ARROW.len() {
+        return Err(LineError::new(
+            chars.len().min(3 + ARROW.len()) + 1,
+            ParseErrorKind::InvalidRowFormat,
+        ));
+    }
+    if chars[2..2 + ARROW.len()].iter().collect::<String>() != ARROW {
+        return Err(LineError::new(3, ParseErrorKind::Missing("' -> '")));
+    }
+    for column in [0, 1, 2 + ARROW.len()] {
+        if !chars[column].is_ascii_alphabetic() {
+            let kind = ParseErrorKind::InvalidChar(chars[column]);
+            return Err(LineError::new(column + 1, kind));
+        }
+    }
+    // Only ASCII letters are left, which are a single byte each
+    Ok((chars[0] as u8, chars[1] as u8, chars[2 + ARROW.len()] as u8))
+}
 // :
 #[derive(Clone, Copy, Debug)]
-struct ListItem {
+pub struct ListItem {
     value: u8,
     next: usize,
 }
@@ -60,7 +94,7 @@ fn try_apply_rules(
     Ok(())
 }
 
-fn apply_rules(value: &mut Vec<ListItem>, rules: &HashMap<(u8, u8), u8>) {
+pub fn apply_rules(value: &mut Vec<ListItem>, rules: &HashMap<(u8, u8), u8>) {
     let mut current = (0, value[0]);
     for _ in 0..value.len() - 1 {
         let next = value[current.1.next];
@@ -75,7 +109,7 @@ fn apply_rules(value: &mut Vec<ListItem>, rules: &HashMap<(u8, u8), u8>) {
     }
 }
 
-fn apply_n_rules_fast(
+pub fn apply_n_rules_fast(
     value: Vec<ListItem>,
     rules: Vec<((u8, u8), u8)>,
     n: usize,
@@ -94,12 +128,12 @@ fn apply_n_rules_fast(
         .tuple_windows()
         .map(|(a, b)| (a.value, b.value));
     for (before, after) in pairs {
-        actual[(before as usize * unique + after as usize)] += 1;
+        actual[before as usize * unique + after as usize] += 1;
     }
 
     let mut rules_matrix = vec![u8::MAX; unique_square];
     for ((before, after), between) in rules {
-        rules_matrix[(before as usize * unique + after as usize)] = between;
+        rules_matrix[before as usize * unique + after as usize] = between;
     }
 
     let mut buffer = vec![0u64; unique_square];
@@ -127,18 +161,50 @@ fn apply_n_rules_fast(
         .minmax()
 }
 
-fn get_min_max_occurance(items: Vec<ListItem>) -> MinMaxResult<u64> {
+pub fn get_min_max_occurance(items: Vec<ListItem>) -> MinMaxResult<u64> {
     let mut lut = HashMap::<u8, u64, _>::new();
     for item in items {
         *lut.entry(item.value).or_default() += 1;
     }
-    lut.into_iter().map(|(_, value)| value).minmax()
+    lut.into_values().minmax()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let (mut init, rules, _) = parse(input.as_bytes())?;
+        let rules_map: HashMap<_, _> = rules.into_iter().collect();
+        for _ in 0..10 {
+            apply_rules(&mut init, &rules_map);
+        }
+        Ok(Box::new(min_max_difference(get_min_max_occurance(init))?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let (init, rules, unique) = parse(input.as_bytes())?;
+        let result = apply_n_rules_fast(init, rules, 40, unique);
+        Ok(Box::new(min_max_difference(result)?))
+    }
+}
+
+fn min_max_difference(result: MinMaxResult<u64>) -> Result<u64, ParseError> {
+    match result {
+        MinMaxResult::MinMax(min, max) => Ok(max - min),
+        MinMaxResult::OneElement(_) => Ok(0),
+        MinMaxResult::NoElements => Err(empty_input()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1_test() {
@@ -157,7 +223,7 @@ mod tests {
     #[test]
     fn part1() {
         let (mut init, rules, _) =
-            parse(std::fs::File::open("puzzleData/day14.txt").unwrap()).unwrap();
+            parse(read_input(14, Variant::Real).unwrap().as_bytes()).unwrap();
         let rules_map: HashMap<_, _> = rules.into_iter().collect();
         for _ in 0..10 {
             apply_rules(&mut init, &rules_map);
@@ -172,7 +238,7 @@ mod tests {
     #[test]
     fn part2() {
         let (init, rules, unique) =
-            parse(std::fs::File::open("puzzleData/day14.txt").unwrap()).unwrap();
+            parse(read_input(14, Variant::Real).unwrap().as_bytes()).unwrap();
         let result = apply_n_rules_fast(init, rules, 40, unique);
         if let MinMaxResult::MinMax(min, max) = result {
             assert_eq!(max - min, 2_158_894_777_814);
@@ -181,7 +247,35 @@ mod tests {
         }
     }
 
-    const TESTDATA: &'static str = "NNCB
+    #[test]
+    fn locate_invalid_rule() {
+        let input = TESTDATA.replace("HH -> N", "HH => N");
+        let error = parse(std::io::Cursor::new(input)).err().unwrap();
+        assert_eq!((4, 3), (error.line, error.column));
+    }
+
+    #[test]
+    fn locate_empty_template() {
+        let input = TESTDATA.replacen("NNCB", "", 1);
+        let error = parse(std::io::Cursor::new(input)).err().unwrap();
+        assert_eq!((1, 1), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Missing(_)));
+    }
+
+    #[test]
+    fn locate_non_ascii_rule() {
+        let error = parse_rule("CH -> ä").unwrap_err();
+        assert_eq!(7, error.column);
+        assert!(matches!(error.kind, ParseErrorKind::InvalidChar('ä')));
+        let error = parse_rule("CH -> ää").unwrap_err();
+        assert_eq!(8, error.column);
+        let error = parse_rule("Cä-> B").unwrap_err();
+        assert_eq!(7, error.column);
+        let error = parse_rule("Cä -> B").unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::InvalidChar('ä')));
+    }
+
+    const TESTDATA: &str = "NNCB
 
 CH -> B
 HH -> N
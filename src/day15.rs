@@ -1,27 +1,26 @@
-use itertools::Itertools;
-use pathfinding::{
-    directed::astar::astar,
-    prelude::{absdiff, Matrix},
-};
+use pathfinding::directed::astar::astar;
+
+use crate::{error::ParseError, Answer, DynError, Grid, Solution};
+
+const DAY: u8 = 15;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 struct Pos(usize, usize);
 
 impl Pos {
     fn distance(&self, other: &Pos) -> u32 {
-        (absdiff(self.0, other.0) + absdiff(self.1, other.1)) as u32
+        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1)) as u32
     }
 
-    fn successors(&self, m: &Matrix<u8>) -> Vec<(Pos, u32)> {
-        m.neighbours((self.0, self.1), false)
+    fn successors(&self, m: &Grid<u8>) -> Vec<(Pos, u32)> {
+        m.neighbours4((self.0, self.1))
             .map(|(x, y)| (Pos(x, y), m[(x, y)] as u32))
             .collect()
     }
 }
 
-fn calculate_shortest_path(m: &Matrix<u8>) -> u32 {
-    let goal = Pos(m.columns - 1, m.rows - 1);
-    dbg!(&goal);
+fn calculate_shortest_path(m: &Grid<u8>) -> u32 {
+    let goal = Pos(m.width() - 1, m.height() - 1);
     let result = astar(
         &Pos(0, 0),
         |p| p.successors(m),
@@ -39,66 +38,63 @@ fn wrap_value(i: u8, offset: u8) -> u8 {
         i => i - 10 + 1,
     }
 }
-fn read_part2_matrix(input: &str) -> Matrix<u8> {
-    let mut vec = input
-        .lines()
-        .map(|c| {
-            let mut vec = Vec::with_capacity(c.bytes().len() * EXPAND as usize);
-            for i in 0..EXPAND {
-                vec.extend(c.bytes().map(|x| wrap_value(x - b'0', i)))
-            }
-            vec
-        })
-        .collect_vec();
-    let height = vec.len();
-    for i in 1..EXPAND {
-        for inner in 0..height {
-            vec.push(vec[inner].iter().map(|x| wrap_value(*x, i)).collect_vec());
-        }
+fn read_part2_matrix(input: &str) -> Result<Grid<u8>, ParseError> {
+    let m = Grid::parse_digits(DAY, input)?;
+    let (width, height) = (m.width(), m.height());
+    Ok(Grid::from_fn(
+        width * EXPAND as usize,
+        height * EXPAND as usize,
+        |(x, y)| wrap_value(m[(x % width, y % height)], (x / width + y / height) as u8),
+    ))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        Grid::parse_digits(DAY, input)?;
+        Ok(())
     }
 
-    vec.into_iter().collect()
-}
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let m = Grid::parse_digits(DAY, input)?;
+        Ok(Box::new(calculate_shortest_path(&m)))
+    }
 
-fn print_matrix(m: &Matrix<u8>) {
-    for y in 0..m.rows {
-        for x in 0..m.columns {
-            print!("{}", m[(y, x)]);
-        }
-        println!();
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(calculate_shortest_path(&read_part2_matrix(
+            input,
+        )?)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
-        let buf = std::fs::read_to_string("puzzleData/day15.txt").unwrap();
-        let m: Matrix<u8> = buf.lines().map(|c| c.bytes().map(|x| x - b'0')).collect();
+        let buf = read_input(15, Variant::Real).unwrap();
+        let m = Grid::parse_digits(DAY, &buf).unwrap();
         assert_eq!(527, calculate_shortest_path(&m));
     }
     #[test]
     fn part1_test() {
-        let m: Matrix<u8> = TESTDATA
-            .lines()
-            .map(|c| c.bytes().map(|x| (x - b'0')))
-            .collect();
+        let m = Grid::parse_digits(DAY, TESTDATA).unwrap();
         assert_eq!(40, calculate_shortest_path(&m));
     }
 
     #[test]
     fn part2_input() {
-        let buf = std::fs::read_to_string("puzzleData/day15.txt").unwrap();
-        let mat = read_part2_matrix(&buf);
+        let buf = read_input(15, Variant::Real).unwrap();
+        let mat = read_part2_matrix(&buf).unwrap();
         assert_eq!(2887, calculate_shortest_path(&mat));
     }
 
     #[test]
     fn part2_test() {
-        let mat = read_part2_matrix(&TESTDATA);
+        let mat = read_part2_matrix(TESTDATA).unwrap();
         assert_eq!(315, calculate_shortest_path(&mat));
     }
 
@@ -106,7 +102,7 @@ mod tests {
     fn wrap_value_10() {
         assert_eq!(2, super::wrap_value(9, 2));
     }
-    const TESTDATA: &'static str = "1163751742
+    const TESTDATA: &str = "1163751742
 1381373672
 2136511328
 3694931569
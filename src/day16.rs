@@ -0,0 +1,428 @@
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub content: Content,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+    Literal(u64),
+    Operator {
+        operator: Operator,
+        length_type: LengthType,
+        packets: Vec<Packet>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+/// How an operator declares the size of its sub-packets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    /// Total length of all sub-packets in bits
+    Bits,
+    /// Number of sub-packets
+    Packets,
+}
+
+const LITERAL_TYPE: u64 = 4;
+
+/// Packet values, which don't fit into the header fields of the encoding
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    #[error("Version {0} doesn't fit into 3 bits")]
+    Version(u8),
+    #[error("{0} bits of sub-packets don't fit into 15 bits")]
+    SubPacketBits(usize),
+    #[error("{0} sub-packets don't fit into 11 bits")]
+    SubPacketCount(usize),
+}
+
+impl Operator {
+    fn from_type(type_id: u64) -> Option<Self> {
+        Some(match type_id {
+            0 => Operator::Sum,
+            1 => Operator::Product,
+            2 => Operator::Minimum,
+            3 => Operator::Maximum,
+            5 => Operator::GreaterThan,
+            6 => Operator::LessThan,
+            7 => Operator::EqualTo,
+            _ => return None,
+        })
+    }
+
+    fn type_id(self) -> u64 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo
+        )
+    }
+}
+
+impl Packet {
+    pub fn version_sum(&self) -> u64 {
+        match &self.content {
+            Content::Literal(_) => self.version as u64,
+            Content::Operator { packets, .. } => {
+                self.version as u64 + packets.iter().map(Packet::version_sum).sum::<u64>()
+            }
+        }
+    }
+
+    pub fn eval(&self) -> u64 {
+        let (operator, packets) = match &self.content {
+            Content::Literal(value) => return *value,
+            Content::Operator {
+                operator, packets, ..
+            } => (operator, packets),
+        };
+        let mut values = packets.iter().map(Packet::eval);
+        match operator {
+            Operator::Sum => values.sum(),
+            Operator::Product => values.product(),
+            Operator::Minimum => values.min().unwrap_or(0),
+            Operator::Maximum => values.max().unwrap_or(0),
+            comparison => {
+                // Parsing guarantees exactly two sub-packets
+                let (a, b) = (values.next().unwrap(), values.next().unwrap());
+                let result = match comparison {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+                result as u64
+            }
+        }
+    }
+
+    /// Encodes the packet as hex, padded with zeros to whole bytes
+    pub fn to_hex(&self) -> Result<String, EncodeError> {
+        let mut bits = Vec::new();
+        self.encode(&mut bits)?;
+        bits.resize(bits.len().div_ceil(8) * 8, false);
+        Ok(bits
+            .chunks(4)
+            .map(|nibble| {
+                let value = nibble.iter().fold(0, |acc, bit| acc << 1 | *bit as u32);
+                char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect())
+    }
+
+    fn encode(&self, bits: &mut Vec<bool>) -> Result<(), EncodeError> {
+        if self.version >= 1 << 3 {
+            return Err(EncodeError::Version(self.version));
+        }
+        push_bits(bits, self.version as u64, 3);
+        match &self.content {
+            Content::Literal(value) => {
+                push_bits(bits, LITERAL_TYPE, 3);
+                let groups = (64 - value.leading_zeros() as usize).div_ceil(4);
+                for group in (0..groups.max(1)).rev() {
+                    bits.push(group > 0);
+                    push_bits(bits, value >> (group * 4), 4);
+                }
+            }
+            Content::Operator {
+                operator,
+                length_type,
+                packets,
+            } => {
+                push_bits(bits, operator.type_id(), 3);
+                match length_type {
+                    LengthType::Bits => {
+                        let mut sub_bits = Vec::new();
+                        for packet in packets {
+                            packet.encode(&mut sub_bits)?;
+                        }
+                        if sub_bits.len() >= 1 << 15 {
+                            return Err(EncodeError::SubPacketBits(sub_bits.len()));
+                        }
+                        bits.push(false);
+                        push_bits(bits, sub_bits.len() as u64, 15);
+                        bits.extend(sub_bits);
+                    }
+                    LengthType::Packets => {
+                        if packets.len() >= 1 << 11 {
+                            return Err(EncodeError::SubPacketCount(packets.len()));
+                        }
+                        bits.push(true);
+                        push_bits(bits, packets.len() as u64, 11);
+                        for packet in packets {
+                            packet.encode(bits)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u64, len: usize) {
+    bits.extend((0..len).rev().map(|i| value >> i & 1 == 1));
+}
+
+struct BitReader {
+    bits: Vec<bool>,
+    pos: usize,
+}
+
+impl BitReader {
+    fn new(line: &str) -> Result<Self, LineError> {
+        let mut bits = Vec::with_capacity(line.len() * 4);
+        for (i, c) in line.chars().enumerate() {
+            let value = c
+                .to_digit(16)
+                .ok_or_else(|| LineError::new(i + 1, ParseErrorKind::InvalidChar(c)))?;
+            push_bits(&mut bits, value as u64, 4);
+        }
+        Ok(Self { bits, pos: 0 })
+    }
+
+    /// Column of the hex digit, which contains the next bit
+    fn column(&self) -> usize {
+        self.pos / 4 + 1
+    }
+
+    fn read(&mut self, len: usize) -> Result<u64, LineError> {
+        let bits = self
+            .bits
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| LineError::new(self.column(), ParseErrorKind::Missing("bits")))?;
+        self.pos += len;
+        Ok(bits.iter().fold(0, |acc, bit| acc << 1 | *bit as u64))
+    }
+
+    fn packet(&mut self) -> Result<Packet, LineError> {
+        let column = self.column();
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)?;
+        let content = match Operator::from_type(type_id) {
+            None => Content::Literal(self.literal()?),
+            Some(operator) => {
+                let (length_type, packets) = if self.read(1)? == 0 {
+                    let len = self.read(15)? as usize;
+                    let end = self.pos + len;
+                    let mut packets = Vec::new();
+                    while self.pos < end {
+                        packets.push(self.packet()?);
+                    }
+                    if self.pos != end {
+                        return Err(LineError::new(column, ParseErrorKind::Invalid("length")));
+                    }
+                    (LengthType::Bits, packets)
+                } else {
+                    let count = self.read(11)?;
+                    let packets = (0..count)
+                        .map(|_| self.packet())
+                        .collect::<Result<_, _>>()?;
+                    (LengthType::Packets, packets)
+                };
+                if operator.is_comparison() && packets.len() != 2 {
+                    return Err(LineError::new(
+                        column,
+                        ParseErrorKind::Invalid("comparison"),
+                    ));
+                }
+                Content::Operator {
+                    operator,
+                    length_type,
+                    packets,
+                }
+            }
+        };
+        Ok(Packet { version, content })
+    }
+
+    fn literal(&mut self) -> Result<u64, LineError> {
+        let column = self.column();
+        let mut value = 0u64;
+        loop {
+            let more = self.read(1)? == 1;
+            if value.leading_zeros() < 4 {
+                return Err(LineError::new(column, ParseErrorKind::Invalid("literal")));
+            }
+            value = value << 4 | self.read(4)?;
+            if !more {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+/// Parses the outermost packet of the transmission, ignoring the padding after it
+pub fn parse(input: &str) -> Result<Packet, ParseError> {
+    let line = input.lines().next().unwrap_or_default().trim();
+    if line.is_empty() {
+        return Err(LineError::new(1, ParseErrorKind::EmptyInput).locate(DAY, 1, line));
+    }
+    BitReader::new(line)
+        .and_then(|mut reader| reader.packet())
+        .map_err(|e| e.locate(DAY, 1, line))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(parse(input)?.version_sum()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(parse(input)?.eval()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal() {
+        let packet = parse("D2FE28").unwrap();
+        assert_eq!(
+            Packet {
+                version: 6,
+                content: Content::Literal(2021)
+            },
+            packet
+        );
+    }
+
+    #[test]
+    fn operators() {
+        let packet = parse("38006F45291200").unwrap();
+        assert_eq!(1, packet.version);
+        match packet.content {
+            Content::Operator {
+                operator,
+                length_type,
+                packets,
+            } => {
+                assert_eq!(Operator::LessThan, operator);
+                assert_eq!(LengthType::Bits, length_type);
+                assert_eq!(
+                    vec![10, 20],
+                    packets.iter().map(Packet::eval).collect::<Vec<_>>()
+                );
+            }
+            literal => panic!("Expected operator, got {:?}", literal),
+        }
+    }
+
+    #[test]
+    fn part1_test() {
+        for (expected, input) in [
+            (16, "8A004A801A8002F478"),
+            (12, "620080001611562C8802118E34"),
+            (23, "C0015000016115A2E0802F182340"),
+            (31, "A0016C880162017C3686B18A3D4780"),
+        ] {
+            assert_eq!(expected, parse(input).unwrap().version_sum(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn part2_test() {
+        for (expected, input) in [
+            (3, "C200B40A82"),
+            (54, "04005AC33890"),
+            (7, "880086C3E88112"),
+            (9, "CE00C43D881120"),
+            (1, "D8005AC2A8F0"),
+            (0, "F600BC2D8F"),
+            (0, "9C005AC2F8F0"),
+            (1, "9C0141080250320F1802104A08"),
+        ] {
+            assert_eq!(expected, parse(input).unwrap().eval(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for input in [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            assert_eq!(input, parse(input).unwrap().to_hex().unwrap());
+        }
+    }
+
+    #[test]
+    fn reject_values_outside_header_fields() {
+        let literal = |version| Packet {
+            version,
+            content: Content::Literal(15),
+        };
+        let operator = |length_type, count| Packet {
+            version: 7,
+            content: Content::Operator {
+                operator: Operator::Sum,
+                length_type,
+                packets: vec![literal(7); count],
+            },
+        };
+        assert_eq!(Err(EncodeError::Version(8)), literal(8).to_hex());
+        assert!(operator(LengthType::Packets, 2047).to_hex().is_ok());
+        assert_eq!(
+            Err(EncodeError::SubPacketCount(2048)),
+            operator(LengthType::Packets, 2048).to_hex()
+        );
+        // Each literal takes 11 bits
+        assert!(operator(LengthType::Bits, 2978).to_hex().is_ok());
+        assert_eq!(
+            Err(EncodeError::SubPacketBits(2979 * 11)),
+            operator(LengthType::Bits, 2979).to_hex()
+        );
+    }
+
+    #[test]
+    fn locate_errors() {
+        let error = parse("D2FG28").unwrap_err();
+        assert_eq!((1, 4), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::InvalidChar('G')));
+        let error = parse("D2FE").unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Missing("bits")));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    day5::Point,
+    error::{LineError, ParseError, ParseErrorKind},
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 17;
+
+/// Target area, which has to be right of and below the launcher
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Target {
+    min: Point,
+    max: Point,
+}
+
+fn parse(input: &str) -> Result<Target, ParseError> {
+    let line = input.lines().next().unwrap_or_default();
+    parse_target(line).map_err(|e| e.locate(DAY, 1, line))
+}
+
+fn parse_target(line: &str) -> Result<Target, LineError> {
+    let ranges = line
+        .strip_prefix("target area: ")
+        .ok_or_else(|| LineError::new(1, ParseErrorKind::Missing("'target area: '")))?;
+    let (x, y) = ranges
+        .split_once(", ")
+        .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing("y range")))?;
+    let (x_min, x_max) = parse_range(line, x, "x=")?;
+    let (y_min, y_max) = parse_range(line, y, "y=")?;
+    if x_min <= 0 || y_max >= 0 {
+        return Err(LineError::at(
+            line,
+            ranges,
+            ParseErrorKind::Invalid("target area"),
+        ));
+    }
+    Ok(Target {
+        min: Point { x: x_min, y: y_min },
+        max: Point { x: x_max, y: y_max },
+    })
+}
+
+fn parse_range(line: &str, part: &str, prefix: &'static str) -> Result<(i32, i32), LineError> {
+    let range = part
+        .strip_prefix(prefix)
+        .ok_or_else(|| LineError::at(line, part, ParseErrorKind::Missing(prefix)))?;
+    let (from, to) = range
+        .split_once("..")
+        .ok_or_else(|| LineError::at(line, &range[range.len()..], ParseErrorKind::Missing("..")))?;
+    let parse = |value: &str| value.parse().map_err(|e| LineError::at(line, value, e));
+    let (from, to): (i32, i32) = (parse(from)?, parse(to)?);
+    Ok((from.min(to), from.max(to)))
+}
+
+impl Target {
+    fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Highest y of the probe, if it is within the target after any step
+    fn apex_of_hit(&self, mut velocity: Point) -> Option<i32> {
+        let mut pos = Point { x: 0, y: 0 };
+        let mut apex = 0;
+        while pos.x <= self.max.x && pos.y >= self.min.y {
+            if self.contains(pos) {
+                return Some(apex);
+            }
+            pos.x += velocity.x;
+            pos.y += velocity.y;
+            velocity.x -= velocity.x.signum();
+            velocity.y -= 1;
+            apex = apex.max(pos.y);
+        }
+        None
+    }
+
+    /// Apex of every velocity, which hits the target
+    ///
+    /// Slower x velocities stop before the target, faster ones overshoot it within the first step.
+    /// A probe launched upwards with vy returns to y=0 with -(vy + 1), so faster ones overshoot
+    /// as well. The same holds for y velocities below the target.
+    fn hits(&self) -> impl Iterator<Item = (Point, i32)> + '_ {
+        let min_vx = min_velocity_to_reach(self.min.x);
+        let vy_range = self.min.y..-self.min.y;
+        (min_vx..=self.max.x)
+            .flat_map(move |x| vy_range.clone().map(move |y| Point { x, y }))
+            .filter_map(move |v| self.apex_of_hit(v).map(|apex| (v, apex)))
+    }
+}
+
+/// Smallest x velocity, which travels at least `distance` before drag stops it
+fn min_velocity_to_reach(distance: i32) -> i32 {
+    // n * (n + 1) / 2 >= distance, in i64 as 8 * distance overflows i32 for large targets
+    let distance = i64::from(distance);
+    let mut n = ((((8 * distance + 1) as f64).sqrt() - 1.0) / 2.0).floor() as i64;
+    while n * (n + 1) / 2 < distance {
+        n += 1;
+    }
+    // At most 65536 for any i32 distance
+    n as i32
+}
+
+fn highest_apex(target: &Target) -> Option<i32> {
+    target.hits().map(|(_, apex)| apex).max()
+}
+
+fn count_velocities(target: &Target) -> usize {
+    target.hits().count()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let apex = highest_apex(&parse(input)?).ok_or("No velocity hits the target")?;
+        Ok(Box::new(apex))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_velocities(&parse(input)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(Some(45), highest_apex(&parse(TEST_INPUT).unwrap()));
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(112, count_velocities(&parse(TEST_INPUT).unwrap()));
+    }
+
+    #[test]
+    fn hits_of_example() {
+        let target = parse(TEST_INPUT).unwrap();
+        assert_eq!(Some(3), target.apex_of_hit(Point { x: 7, y: 2 }));
+        assert_eq!(Some(0), target.apex_of_hit(Point { x: 6, y: 0 }));
+        assert_eq!(None, target.apex_of_hit(Point { x: 17, y: -4 }));
+        assert_eq!(6, min_velocity_to_reach(20));
+        assert_eq!(5, min_velocity_to_reach(15));
+        assert_eq!(4472, min_velocity_to_reach(10_000_000));
+        assert_eq!(65536, min_velocity_to_reach(i32::MAX));
+    }
+
+    #[test]
+    fn locate_invalid_range() {
+        let error = parse("target area: x=20..3a, y=-10..-5").unwrap_err();
+        assert_eq!((1, 20), (error.line, error.column));
+        let error = parse("target area: x=20..30, -10..-5").unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Missing("y=")));
+        assert_eq!(24, error.column);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt::Display, io::Read, iter::Peekable, ops::Add, str::CharIndices, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 18;
+
+/// Snailfish number, which is either a regular number or a pair of snailfish numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+fn parse(r: impl Read) -> Result<Vec<Number>, ParseError> {
+    parse_lines(DAY, r, str::parse).collect()
+}
+
+impl Number {
+    fn pair(left: Number, right: Number) -> Self {
+        Number::Pair(Box::new(left), Box::new(right))
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            Number::Regular(value) => *value,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    /// Explodes the leftmost pair nested in four pairs and returns the values,
+    /// which still have to be added to its left and right neighbours
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let Number::Pair(left, right) = self else {
+            return None;
+        };
+        if depth >= 4 {
+            if let (Number::Regular(l), Number::Regular(r)) = (left.as_ref(), right.as_ref()) {
+                let carry = (Some(*l), Some(*r));
+                *self = Number::Regular(0);
+                return Some(carry);
+            }
+        }
+        if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+            if let Some(value) = carry_right {
+                right.add_to_edge(value, true);
+            }
+            return Some((carry_left, None));
+        }
+        if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+            if let Some(value) = carry_left {
+                left.add_to_edge(value, false);
+            }
+            return Some((None, carry_right));
+        }
+        None
+    }
+
+    fn add_to_edge(&mut self, value: u32, leftmost: bool) {
+        match self {
+            Number::Regular(v) => *v += value,
+            Number::Pair(left, _) if leftmost => left.add_to_edge(value, leftmost),
+            Number::Pair(_, right) => right.add_to_edge(value, leftmost),
+        }
+    }
+
+    /// Splits the leftmost regular number of 10 or more
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(value) if *value >= 10 => {
+                *self = Number::pair(
+                    Number::Regular(*value / 2),
+                    Number::Regular(value.div_ceil(2)),
+                );
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut sum = Number::pair(self, rhs);
+        sum.reduce();
+        sum
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Regular(value) => write!(f, "{}", value),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+struct Parser<'a> {
+    line: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn number(&mut self) -> Result<Number, LineError> {
+        match self.chars.next() {
+            Some((_, '[')) => {
+                let left = self.number()?;
+                self.expect(',')?;
+                let right = self.number()?;
+                self.expect(']')?;
+                Ok(Number::pair(left, right))
+            }
+            Some((start, c)) if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((i, _)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = i + 1;
+                }
+                let digits = &self.line[start..end];
+                Ok(Number::Regular(
+                    digits
+                        .parse()
+                        .map_err(|e| LineError::at(self.line, digits, e))?,
+                ))
+            }
+            Some((i, c)) => Err(self.invalid_char(i, c)),
+            None => Err(LineError::after(
+                self.line,
+                ParseErrorKind::Missing("number"),
+            )),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), LineError> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => Err(self.invalid_char(i, c)),
+            None => Err(LineError::after(
+                self.line,
+                ParseErrorKind::Missing(if expected == ',' { "','" } else { "']'" }),
+            )),
+        }
+    }
+
+    fn invalid_char(&self, i: usize, c: char) -> LineError {
+        LineError::at(self.line, &self.line[i..], ParseErrorKind::InvalidChar(c))
+    }
+}
+
+impl FromStr for Number {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            line: s,
+            chars: s.char_indices().peekable(),
+        };
+        let number = parser.number()?;
+        match parser.chars.next() {
+            Some((i, c)) => Err(parser.invalid_char(i, c)),
+            None => Ok(number),
+        }
+    }
+}
+
+fn sum(numbers: impl IntoIterator<Item = Number>) -> Option<Number> {
+    numbers.into_iter().reduce(Add::add)
+}
+
+/// Largest magnitude of the sum of any two different numbers in either order
+fn best_pairwise_sum(numbers: &[Number]) -> Option<u32> {
+    numbers
+        .iter()
+        .permutations(2)
+        .map(|pair| (pair[0].clone() + pair[1].clone()).magnitude())
+        .max()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let sum = sum(parse(input.as_bytes())?).ok_or("No numbers to add")?;
+        Ok(Box::new(sum.magnitude()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let best = best_pairwise_sum(&parse(input.as_bytes())?).ok_or("Less than two numbers")?;
+        Ok(Box::new(best))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(s: &str) -> Number {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let input = "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]";
+        assert_eq!(input, number(input).to_string());
+    }
+
+    #[test]
+    fn add_and_reduce() {
+        assert_eq!(
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            (number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]")).to_string()
+        );
+        let sum = sum(["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"].map(number));
+        assert_eq!("[[[[5,0],[7,4]],[5,5]],[6,6]]", sum.unwrap().to_string());
+    }
+
+    #[test]
+    fn magnitude() {
+        assert_eq!(143, number("[[1,2],[[3,4],5]]").magnitude());
+        assert_eq!(
+            1384,
+            number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").magnitude()
+        );
+    }
+
+    #[test]
+    fn part1_test() {
+        let sum = sum(parse(TEST_INPUT.as_bytes()).unwrap()).unwrap();
+        assert_eq!(
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+            sum.to_string()
+        );
+        assert_eq!(3488, sum.magnitude());
+    }
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(
+            Some(3805),
+            best_pairwise_sum(&parse(TEST_INPUT.as_bytes()).unwrap())
+        );
+    }
+
+    #[test]
+    fn locate_invalid_number() {
+        let error = parse("[1,2]\n[[1,2];3]".as_bytes()).unwrap_err();
+        assert_eq!((2, 7), (error.line, error.column));
+        let error = parse("[1,2".as_bytes()).unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Missing("']'")));
+        assert_eq!(5, error.column);
+    }
+
+    const TEST_INPUT: &str = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]";
+}
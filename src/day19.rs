@@ -0,0 +1,385 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::numbered_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 19;
+const MIN_OVERLAP: usize = 12;
+
+pub type Vec3 = [i32; 3];
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Scanners {0:?} don't overlap with any other scanner")]
+    Unaligned(Vec<usize>),
+}
+
+/// One of the 24 orientations, which takes axis `i` of the result from axis `axes[i]` of the input
+/// and flips it by `signs[i]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        axes: [0, 1, 2],
+        signs: [1, 1, 1],
+    };
+
+    /// All axis permutations and sign flips, which keep the coordinate system right-handed
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        [0, 1, 2]
+            .into_iter()
+            .permutations(3)
+            .cartesian_product((0..8).map(|bits| [0, 1, 2].map(|i| 1 - 2 * (bits >> i & 1))))
+            .map(|(axes, signs)| Rotation {
+                axes: [axes[0], axes[1], axes[2]],
+                signs,
+            })
+            .filter(|r| r.determinant() == 1)
+    }
+
+    fn determinant(&self) -> i32 {
+        let inversions = (0..3)
+            .tuple_combinations()
+            .filter(|&(i, j)| self.axes[i] > self.axes[j])
+            .count();
+        let parity = if inversions % 2 == 0 { 1 } else { -1 };
+        parity * self.signs.iter().product::<i32>()
+    }
+
+    pub fn apply(&self, v: Vec3) -> Vec3 {
+        [0, 1, 2].map(|i| self.signs[i] * v[self.axes[i]])
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        let mut inverse = *self;
+        for i in 0..3 {
+            inverse.axes[self.axes[i]] = i;
+            inverse.signs[self.axes[i]] = self.signs[i];
+        }
+        inverse
+    }
+
+    /// Rotation as matrix, which is multiplied with column vectors
+    pub fn matrix(&self) -> [[i32; 3]; 3] {
+        [0, 1, 2].map(|row| [0, 1, 2].map(|col| (self.axes[row] == col) as i32 * self.signs[row]))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    pub id: usize,
+    pub beacons: Vec<Vec3>,
+}
+
+/// Pose of a scanner relative to scanner 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub scanner: usize,
+    pub position: Vec3,
+    pub rotation: Rotation,
+}
+
+impl Placement {
+    fn transform(&self, v: Vec3) -> Vec3 {
+        add(self.rotation.apply(v), self.position)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    /// Placement of every scanner, ordered by scanner id
+    pub placements: Vec<Placement>,
+    pub beacons: HashSet<Vec3>,
+}
+
+impl Map {
+    pub fn max_scanner_distance(&self) -> i32 {
+        self.placements
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| manhattan(a.position, b.position))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn manhattan(a: Vec3, b: Vec3) -> i32 {
+    sub(a, b).iter().map(|c| c.abs()).sum()
+}
+
+fn parse(r: impl Read) -> Result<Vec<Scanner>, ParseError> {
+    let mut scanners: Vec<Scanner> = Vec::new();
+    for line in numbered_lines(DAY, r) {
+        let (no, line) = line?;
+        let locate = |e: LineError| e.locate(DAY, no, &line);
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("--- scanner ") {
+            let id = header.strip_suffix(" ---").ok_or_else(|| {
+                locate(LineError::after(&line, ParseErrorKind::Missing("' ---'")))
+            })?;
+            let id = id
+                .parse()
+                .map_err(|e| locate(LineError::at(&line, id, e)))?;
+            scanners.push(Scanner {
+                id,
+                beacons: Vec::new(),
+            });
+        } else {
+            let scanner = scanners.last_mut().ok_or_else(|| {
+                locate(LineError::new(1, ParseErrorKind::Missing("scanner header")))
+            })?;
+            scanner.beacons.push(parse_beacon(&line).map_err(locate)?);
+        }
+    }
+    Ok(scanners)
+}
+
+fn parse_beacon(line: &str) -> Result<Vec3, LineError> {
+    let mut values = line.split(',');
+    let mut next = |name| {
+        let value = values
+            .next()
+            .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing(name)))?;
+        value.parse().map_err(|e| LineError::at(line, value, e))
+    };
+    let beacon = [next("x")?, next("y")?, next("z")?];
+    if let Some(extra) = values.next() {
+        return Err(LineError::at(
+            line,
+            extra,
+            ParseErrorKind::Unexpected("value"),
+        ));
+    }
+    Ok(beacon)
+}
+
+/// Pose of `scanner`, if at least 12 of its beacons match the already placed `beacons`
+fn align(beacons: &[Vec3], scanner: &Scanner) -> Option<(Rotation, Vec3)> {
+    Rotation::all().find_map(|rotation| {
+        let mut offsets = HashMap::new();
+        for relative in scanner.beacons.iter().map(|b| rotation.apply(*b)) {
+            for known in beacons {
+                let count = offsets.entry(sub(*known, relative)).or_insert(0);
+                *count += 1;
+                if *count >= MIN_OVERLAP {
+                    return Some((rotation, sub(*known, relative)));
+                }
+            }
+        }
+        None
+    })
+}
+
+/// Places all scanners relative to the first one, by aligning unplaced scanners to placed ones
+pub fn assemble(scanners: &[Scanner]) -> Result<Map, Error> {
+    let Some(first) = scanners.first() else {
+        return Ok(Map {
+            placements: Vec::new(),
+            beacons: HashSet::new(),
+        });
+    };
+    let mut placed = vec![(
+        Placement {
+            scanner: first.id,
+            position: [0, 0, 0],
+            rotation: Rotation::IDENTITY,
+        },
+        first.beacons.clone(),
+    )];
+    let mut unplaced = scanners[1..].iter().collect::<Vec<_>>();
+    let mut next = 0;
+    while next < placed.len() {
+        let reference = placed[next].1.clone();
+        unplaced.retain(|scanner| match align(&reference, scanner) {
+            Some((rotation, position)) => {
+                let placement = Placement {
+                    scanner: scanner.id,
+                    position,
+                    rotation,
+                };
+                let beacons = scanner
+                    .beacons
+                    .iter()
+                    .map(|b| placement.transform(*b))
+                    .collect();
+                placed.push((placement, beacons));
+                false
+            }
+            None => true,
+        });
+        next += 1;
+    }
+    if !unplaced.is_empty() {
+        return Err(Error::Unaligned(unplaced.iter().map(|s| s.id).collect()));
+    }
+    let beacons = placed.iter().flat_map(|(_, b)| b.iter().copied()).collect();
+    let mut placements = placed.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+    placements.sort_by_key(|p| p.scanner);
+    Ok(Map {
+        placements,
+        beacons,
+    })
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let map = assemble(&parse(input.as_bytes())?)?;
+        Ok(Box::new(map.beacons.len()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let map = assemble(&parse(input.as_bytes())?)?;
+        Ok(Box::new(map.max_scanner_distance()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inputs::{read_input, Variant},
+        utils::pseudo_random,
+    };
+
+    /// Deterministic pseudo random beacons, which are unlikely to form accidental overlaps
+    fn random_beacons(seed: u64, count: usize) -> Vec<Vec3> {
+        let mut random = pseudo_random(seed);
+        let mut next = move || random(2001) as i32 - 1000;
+        (0..count).map(|_| [next(), next(), next()]).collect()
+    }
+
+    /// Readings of a scanner at `placement` for beacons given relative to scanner 0
+    fn readings(placement: &Placement, beacons: &[Vec3]) -> Vec<Vec3> {
+        let inverse = placement.rotation.inverse();
+        beacons
+            .iter()
+            .map(|b| inverse.apply(sub(*b, placement.position)))
+            .collect()
+    }
+
+    #[test]
+    fn rotations() {
+        let all = Rotation::all().collect::<Vec<_>>();
+        assert_eq!(24, all.len());
+        assert_eq!(24, all.iter().map(|r| r.apply([1, 2, 3])).unique().count());
+        for rotation in all {
+            assert_eq!(
+                [1, 2, 3],
+                rotation.inverse().apply(rotation.apply([1, 2, 3]))
+            );
+        }
+        assert_eq!(
+            [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+            Rotation::IDENTITY.matrix()
+        );
+    }
+
+    #[test]
+    fn assemble_chain_of_scanners() {
+        let beacons = random_beacons(19, 44);
+        let rotations = Rotation::all().collect::<Vec<_>>();
+        let poses = [
+            Placement {
+                scanner: 1,
+                position: [68, -1246, -43],
+                rotation: rotations[7],
+            },
+            Placement {
+                scanner: 2,
+                position: [1105, -1205, 1229],
+                rotation: rotations[18],
+            },
+        ];
+        // Scanner 0 and 1 share beacons 10..22, scanner 1 and 2 share beacons 20..32
+        let scanners = vec![
+            Scanner {
+                id: 0,
+                beacons: beacons[..22].to_vec(),
+            },
+            Scanner {
+                id: 1,
+                beacons: readings(&poses[0], &beacons[10..32]),
+            },
+            Scanner {
+                id: 2,
+                beacons: readings(&poses[1], &beacons[20..]),
+            },
+        ];
+        let map = assemble(&scanners).unwrap();
+        assert_eq!(&poses[..], &map.placements[1..]);
+        assert_eq!(44, map.beacons.len());
+        assert_eq!(1105 + 1205 + 1229, map.max_scanner_distance());
+    }
+
+    #[test]
+    fn unaligned_scanner() {
+        let scanners = vec![
+            Scanner {
+                id: 0,
+                beacons: random_beacons(1, 20),
+            },
+            Scanner {
+                id: 1,
+                beacons: random_beacons(2, 20),
+            },
+        ];
+        assert!(matches!(assemble(&scanners), Err(Error::Unaligned(ids)) if ids == [1]));
+    }
+
+    #[test]
+    fn parse_reports() {
+        let scanners = parse(
+            "--- scanner 0 ---\n404,-588,-901\n-1,2,3\n\n--- scanner 1 ---\n686,422,578".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(2, scanners.len());
+        assert_eq!(vec![[404, -588, -901], [-1, 2, 3]], scanners[0].beacons);
+        let error = parse("--- scanner 0 ---\n404,-588".as_bytes()).unwrap_err();
+        assert_eq!((2, 9), (error.line, error.column));
+        let error = parse("404,-588,-901".as_bytes()).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ParseErrorKind::Missing("scanner header")
+        ));
+        let error = parse("--- scanner 0 ---\n1,2,3,4".as_bytes()).unwrap_err();
+        assert_eq!((2, 7), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Unexpected(_)));
+    }
+
+    #[test]
+    fn example() {
+        let input = read_input(19, Variant::Example).unwrap();
+        let map = assemble(&parse(input.as_bytes()).unwrap()).unwrap();
+        assert_eq!(79, map.beacons.len());
+        assert_eq!(3621, map.max_scanner_distance());
+    }
+}
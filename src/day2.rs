@@ -1,104 +1,736 @@
-use std::{io::Read, str::FromStr};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    io::Read,
+    str::FromStr,
+};
 
-use itertools::process_results;
-use simple_lines::ReadExt;
+use itertools::Itertools;
 
-enum Command {
+use crate::{
+    error::{ErrorPolicy, LineError, ParseError, ParseErrorKind},
+    utils::{numbered_lines, parse_lines, parse_lines_with},
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
     Up(i32),
     Down(i32),
     Forward(i32),
-}
-
-#[derive(thiserror::Error, Debug)]
-enum Error {
-    #[error("Invalid Command: {0}")]
-    ParseCommand(String),
-    #[error("Not enough arguments")]
-    InvalidNumberOfArguments(&'static str),
-    #[error("Not enough arguments")]
-    ValueIsNotANumber(),
+    Back(i32),
 }
 
 impl FromStr for Command {
-    type Err = Error;
+    type Err = LineError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split(' ');
         let command = parts
             .next()
-            .ok_or(Error::InvalidNumberOfArguments("command"))?;
+            .filter(|command| !command.is_empty())
+            .ok_or_else(|| LineError::after(s, ParseErrorKind::Missing("command")))?;
         let number = parts
             .next()
-            .ok_or(Error::InvalidNumberOfArguments("number"))?
-            .parse()
-            .map_err(|_| Error::ValueIsNotANumber())?;
+            .ok_or_else(|| LineError::after(s, ParseErrorKind::Missing("number")))?;
+        let number = number.parse().map_err(|e| LineError::at(s, number, e))?;
         match command {
             "forward" => Ok(Command::Forward(number)),
             "up" => Ok(Command::Up(number)),
             "down" => Ok(Command::Down(number)),
-            _ => Err(Error::ParseCommand(command.to_owned())),
+            "back" => Ok(Command::Back(number)),
+            _ => Err(LineError::at(
+                s,
+                command,
+                ParseErrorKind::UnknownCommand(command.to_owned()),
+            )),
         }
     }
 }
 
-#[derive(Debug, Default)]
-struct Position {
-    x: i32,
-    y: i32,
+pub fn parse(input: impl Read) -> impl Iterator<Item = Result<Command, ParseError>> {
+    parse_lines(DAY, input, str::parse)
 }
 
-fn navigate(input: impl Iterator<Item = Command>) -> Position {
-    let mut position = Position::default();
+/// Statement of a navigation script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Command(Command),
+    Repeat(usize, Vec<Statement>),
+    Call(String),
+}
+
+/// Navigation script of commands, `repeat N { .. }` blocks and calls of the subroutines defined
+/// by `sub name { .. }`, where `#` starts a comment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    pub main: Vec<Statement>,
+    pub subroutines: HashMap<String, Vec<Statement>>,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ScriptError {
+    #[error("Subroutine '{0}' is not defined")]
+    UnknownSubroutine(String),
+    #[error("Subroutine '{0}' calls itself")]
+    Recursion(String),
+}
+
+enum Block {
+    Repeat(usize),
+    Subroutine(String),
+}
+
+/// Block, which is still open, with the line it was opened at
+struct OpenBlock {
+    block: Block,
+    statements: Vec<Statement>,
+    line: (usize, String),
+}
+
+pub fn parse_script(input: impl Read) -> Result<Script, ParseError> {
+    let mut script = Script::default();
+    let mut open: Vec<OpenBlock> = Vec::new();
+    for line in numbered_lines(DAY, input) {
+        let (no, line) = line?;
+        let code = line.split('#').next().unwrap_or_default().trim();
+        let locate = |e: LineError| e.locate(DAY, no, &line);
+        if code.is_empty() {
+            continue;
+        }
+        if code == "}" {
+            let closed = open.pop().ok_or_else(|| {
+                locate(LineError::at(&line, code, ParseErrorKind::InvalidChar('}')))
+            })?;
+            match closed.block {
+                Block::Repeat(times) => innermost(&mut open, &mut script.main)
+                    .push(Statement::Repeat(times, closed.statements)),
+                Block::Subroutine(name) => {
+                    script.subroutines.insert(name, closed.statements);
+                }
+            }
+            continue;
+        }
+        let parts = code.split(' ').collect::<Vec<_>>();
+        let block = match parts[..] {
+            ["repeat", times, "{"] => Block::Repeat(
+                times
+                    .parse()
+                    .map_err(|e| locate(LineError::at(&line, times, e)))?,
+            ),
+            ["sub", name, "{"] => {
+                if !open.is_empty() {
+                    return Err(locate(LineError::at(
+                        &line,
+                        code,
+                        ParseErrorKind::Invalid("nested subroutine"),
+                    )));
+                }
+                if script.subroutines.contains_key(name) {
+                    return Err(locate(LineError::at(
+                        &line,
+                        name,
+                        ParseErrorKind::Invalid("subroutine, which is already defined"),
+                    )));
+                }
+                Block::Subroutine(name.to_owned())
+            }
+            ["repeat", _] | ["sub", _] => {
+                return Err(locate(LineError::at(
+                    &line,
+                    &code[code.len()..],
+                    ParseErrorKind::Missing("{"),
+                )))
+            }
+            ["call", name] => {
+                innermost(&mut open, &mut script.main).push(Statement::Call(name.to_owned()));
+                continue;
+            }
+            _ => {
+                let command = code.parse().map_err(|e: LineError| {
+                    let mut located = LineError::at(&line, code, e.kind);
+                    located.column += e.column - 1;
+                    locate(located)
+                })?;
+                innermost(&mut open, &mut script.main).push(Statement::Command(command));
+                continue;
+            }
+        };
+        open.push(OpenBlock {
+            block,
+            statements: Vec::new(),
+            line: (no, line.to_string()),
+        });
+    }
+    match open.pop() {
+        Some(OpenBlock {
+            line: (no, line), ..
+        }) => Err(LineError::after(&line, ParseErrorKind::Missing("}")).locate(DAY, no, &line)),
+        None => Ok(script),
+    }
+}
+
+/// Statements of the innermost open block or of the main part
+fn innermost<'a>(
+    open: &'a mut [OpenBlock],
+    main: &'a mut Vec<Statement>,
+) -> &'a mut Vec<Statement> {
+    match open.last_mut() {
+        Some(block) => &mut block.statements,
+        None => main,
+    }
+}
+
+impl Script {
+    /// Commands of the main statements, where blocks are repeated and calls are replaced by
+    /// the statements of their subroutine
+    pub fn expand(&self) -> Result<Vec<Command>, ScriptError> {
+        let mut commands = Vec::new();
+        self.expand_into(&self.main, &mut Vec::new(), &mut commands)?;
+        Ok(commands)
+    }
+
+    fn expand_into<'a>(
+        &'a self,
+        statements: &'a [Statement],
+        calls: &mut Vec<&'a str>,
+        commands: &mut Vec<Command>,
+    ) -> Result<(), ScriptError> {
+        for statement in statements {
+            match statement {
+                Statement::Command(command) => commands.push(*command),
+                Statement::Repeat(times, body) => {
+                    for _ in 0..*times {
+                        self.expand_into(body, calls, commands)?;
+                    }
+                }
+                Statement::Call(name) => {
+                    if calls.contains(&name.as_str()) {
+                        return Err(ScriptError::Recursion(name.clone()));
+                    }
+                    let body = self
+                        .subroutines
+                        .get(name)
+                        .ok_or_else(|| ScriptError::UnknownSubroutine(name.clone()))?;
+                    calls.push(name);
+                    self.expand_into(body, calls, commands)?;
+                    calls.pop();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Interpretation of the commands, which turns them into moves of the submarine
+pub trait NavigationModel {
+    type State: Default;
 
+    fn apply(&self, state: &mut Self::State, command: &Command);
+}
+
+/// Drives the model through all commands, starting at its default state
+pub fn navigate_with<M: NavigationModel>(
+    model: &M,
+    input: impl IntoIterator<Item = Command>,
+) -> M::State {
+    let mut state = M::State::default();
     for command in input {
-        match command {
+        model.apply(&mut state, &command);
+    }
+    state
+}
+
+pub fn navigate_reader_with<M: NavigationModel>(
+    model: &M,
+    input: impl Read,
+) -> Result<M::State, ParseError> {
+    let parsed = parse_lines_with(DAY, input, ErrorPolicy::FailFast, str::parse, |commands| {
+        navigate_with(model, commands)
+    })?;
+    Ok(parsed.result)
+}
+
+/// Up and down change the depth directly
+pub struct Simple;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl NavigationModel for Simple {
+    type State = Position;
+
+    fn apply(&self, position: &mut Position, command: &Command) {
+        match *command {
             Command::Up(y) => position.y -= y,
             Command::Down(y) => position.y += y,
             Command::Forward(x) => position.x += x,
+            Command::Back(x) => position.x -= x,
         }
     }
+}
 
-    position
+fn navigate(input: impl Iterator<Item = Command>) -> Position {
+    navigate_with(&Simple, input)
 }
 
-fn navigate_reader(input: impl Read) -> Result<Position, Box<dyn std::error::Error>> {
-    let data = input.lines_rc().map(|x| Ok(x?.parse()?));
-    #[allow(clippy::redundant_closure)]
-    process_results(data, |iter| navigate(iter))
+fn navigate_reader(input: impl Read) -> Result<Position, ParseError> {
+    navigate_reader_with(&Simple, input)
 }
 
-#[derive(Debug, Default)]
-struct PositionV2 {
-    x: i32,
-    y: i32,
-    aim: i32,
+/// Up and down change the aim, which determines how much moving forward changes the depth
+pub struct Aim;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionV2 {
+    pub x: i32,
+    pub y: i32,
+    pub aim: i32,
 }
 
-fn navigate_v2(input: impl Iterator<Item = Command>) -> PositionV2 {
-    let mut position = PositionV2::default();
-    for command in input {
-        match command {
+impl NavigationModel for Aim {
+    type State = PositionV2;
+
+    fn apply(&self, position: &mut PositionV2, command: &Command) {
+        match *command {
             Command::Up(y) => position.aim -= y,
             Command::Down(y) => position.aim += y,
             Command::Forward(x) => {
                 position.x += x;
                 position.y += position.aim * x;
             }
+            Command::Back(x) => {
+                position.x -= x;
+                position.y -= position.aim * x;
+            }
         }
     }
-    position
 }
 
-fn navigate_reader_v2(input: impl Read) -> Result<PositionV2, Box<dyn std::error::Error>> {
-    let data = input.lines_rc().map(|x| Ok(x?.parse()?));
-    #[allow(clippy::redundant_closure)]
-    process_results(data, |iter| navigate_v2(iter))
+fn navigate_v2(input: impl Iterator<Item = Command>) -> PositionV2 {
+    navigate_with(&Aim, input)
+}
+
+fn navigate_reader_v2(input: impl Read) -> Result<PositionV2, ParseError> {
+    navigate_reader_with(&Aim, input)
+}
+
+/// State, which can be recorded as a point of a trajectory
+pub trait Waypoint: Clone {
+    fn x(&self) -> i32;
+    fn depth(&self) -> i32;
+
+    /// Further values, which are exported next to the position
+    fn extra(&self) -> Vec<(&'static str, i32)> {
+        Vec::new()
+    }
+}
+
+impl Waypoint for Position {
+    fn x(&self) -> i32 {
+        self.x
+    }
+
+    fn depth(&self) -> i32 {
+        self.y
+    }
+}
+
+impl Waypoint for PositionV2 {
+    fn x(&self) -> i32 {
+        self.x
+    }
+
+    fn depth(&self) -> i32 {
+        self.y
+    }
+
+    fn extra(&self) -> Vec<(&'static str, i32)> {
+        vec![("aim", self.aim)]
+    }
+}
+
+/// State right after the command with the 0-based index `command`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<S> {
+    pub command: usize,
+    pub state: S,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory<S> {
+    pub start: S,
+    pub steps: Vec<Step<S>>,
+}
+
+/// Drives the model like `navigate_with`, but keeps the state after every command
+pub fn record_with<M: NavigationModel>(
+    model: &M,
+    input: impl IntoIterator<Item = Command>,
+) -> Trajectory<M::State>
+where
+    M::State: Clone,
+{
+    let start = M::State::default();
+    let mut state = start.clone();
+    let mut steps = Vec::new();
+    for (command, c) in input.into_iter().enumerate() {
+        model.apply(&mut state, &c);
+        steps.push(Step {
+            command,
+            state: state.clone(),
+        });
+    }
+    Trajectory { start, steps }
+}
+
+pub fn record_reader_with<M: NavigationModel>(
+    model: &M,
+    input: impl Read,
+) -> Result<Trajectory<M::State>, ParseError>
+where
+    M::State: Clone,
+{
+    let parsed = parse_lines_with(DAY, input, ErrorPolicy::FailFast, str::parse, |commands| {
+        record_with(model, commands)
+    })?;
+    Ok(parsed.result)
+}
+
+impl<S: Waypoint> Trajectory<S> {
+    pub fn end(&self) -> &S {
+        self.steps.last().map_or(&self.start, |step| &step.state)
+    }
+
+    /// First step, after which the depth is larger than `threshold`
+    pub fn first_deeper_than(&self, threshold: i32) -> Option<&Step<S>> {
+        self.steps
+            .iter()
+            .find(|step| step.state.depth() > threshold)
+    }
+
+    fn states(&self) -> impl Iterator<Item = &S> {
+        std::iter::once(&self.start).chain(self.steps.iter().map(|step| &step.state))
+    }
+
+    /// One row per state, where the command of the start is left empty
+    pub fn to_csv(&self) -> String {
+        let mut csv = std::iter::once("command,x,depth")
+            .chain(self.start.extra().iter().map(|(name, _)| *name))
+            .join(",");
+        let commands = std::iter::once(None).chain(self.steps.iter().map(|s| Some(s.command)));
+        for (command, state) in commands.zip(self.states()) {
+            let command = command.map_or(String::new(), |c| c.to_string());
+            let values = [state.x(), state.depth()]
+                .into_iter()
+                .chain(state.extra().into_iter().map(|(_, value)| value));
+            csv.push('\n');
+            csv.push_str(
+                &std::iter::once(command)
+                    .chain(values.map(|v| v.to_string()))
+                    .join(","),
+            );
+        }
+        csv
+    }
+
+    /// Polyline through all positions, where the depth grows downwards like the y axis of SVG
+    pub fn to_svg(&self) -> String {
+        let range = |values: Vec<i32>| values.into_iter().minmax().into_option();
+        let (min_x, max_x) = range(self.states().map(S::x).collect()).expect("start exists");
+        let (min_depth, max_depth) =
+            range(self.states().map(S::depth).collect()).expect("start exists");
+        let points = self
+            .states()
+            .map(|state| format!("{},{}", state.x(), state.depth()))
+            .join(" ");
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n\
+             <polyline fill=\"none\" stroke=\"black\" points=\"{}\"/>\n</svg>",
+            min_x,
+            min_depth,
+            (max_x - min_x).max(1),
+            (max_depth - min_depth).max(1),
+            points
+        )
+    }
+}
+
+/// Limits for plans of the aim based model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constraints {
+    /// Largest number a single command may carry
+    pub max_step: i32,
+    /// Plans, which are shorter, are padded with `down 1` and `up 1` at the end, which only
+    /// change the final aim
+    pub min_commands: usize,
+}
+
+impl Default for Constraints {
+    /// Steps of 1 to 9 like in the puzzle input
+    fn default() -> Self {
+        Self {
+            max_step: 9,
+            min_commands: 0,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PlanError {
+    #[error("Steps of at most {0} can't move")]
+    InvalidStep(i32),
+    #[error("Position {x} with depth {depth} can't be reached by moving forward")]
+    Unreachable { x: i32, depth: i32 },
+}
+
+/// Search for plans with a fixed number of forward and aim commands
+///
+/// A plan consists of breakpoints, where the aim is changed before moving forward again.
+/// Changing the aim by A with `x` left until the target changes the final depth by `A * x`,
+/// so only the remaining distance and depth matter, which allows memoizing failed searches.
+struct Planner {
+    step: i64,
+    failed: HashSet<(i64, i64, usize, usize)>,
+}
+
+/// Outcome of entering a state of the search
+enum Visit {
+    Reached,
+    Failed,
+    Search(Breakpoint),
+}
+
+/// Breakpoint of a plan on the search stack, with the moves not tried yet
+struct Breakpoint {
+    remaining: i64,
+    depth: i64,
+    forwards: usize,
+    aims: usize,
+    /// Length of the plan before the commands of this breakpoint
+    len: usize,
+    /// Aims left to try, the most promising one last
+    candidates: Vec<i64>,
+    /// Aim, which is tried, and the next forward step to try with it
+    current: Option<(i64, i64)>,
+}
+
+impl Breakpoint {
+    /// Next aim and forward step, trying larger steps first
+    fn next_move(&mut self, step: i64) -> Option<(i64, i64)> {
+        loop {
+            if let Some((aim, forward)) = self.current.filter(|(_, forward)| *forward > 0) {
+                self.current = Some((aim, forward - 1));
+                return Some((aim, forward));
+            }
+            let aim = self.candidates.pop()?;
+            self.current = Some((aim, step.min(self.remaining)));
+        }
+    }
+}
+
+impl Planner {
+    fn cost(&self, aim: i64) -> usize {
+        ((aim.abs() + self.step - 1) / self.step) as usize
+    }
+
+    fn reach(&self, aims: usize, remaining: i64) -> i64 {
+        aims as i64 * self.step * remaining
+    }
+
+    fn visit(&self, remaining: i64, depth: i64, forwards: usize, aims: usize, len: usize) -> Visit {
+        if remaining == 0 {
+            return if depth == 0 {
+                Visit::Reached
+            } else {
+                Visit::Failed
+            };
+        }
+        if (forwards as i64) < (remaining + self.step - 1) / self.step
+            || depth.abs() > self.reach(aims, remaining)
+            || self.failed.contains(&(remaining, depth, forwards, aims))
+        {
+            return Visit::Failed;
+        }
+        let later = self.reach(aims, remaining - 1);
+        let lowest = -(later - depth).div_euclid(remaining);
+        let highest = (depth + later).div_euclid(remaining);
+        let closest = depth.div_euclid(remaining);
+        let candidates = (lowest.max(-self.reach(aims, 1))..=highest.min(self.reach(aims, 1)))
+            .filter(|aim| {
+                let cost = self.cost(*aim);
+                let rest = depth - aim * remaining;
+                cost <= aims && rest.abs() <= self.reach(aims - cost, remaining - 1)
+            })
+            .sorted_by_key(|aim| Reverse((self.cost(*aim), (aim - closest).abs())))
+            .collect();
+        Visit::Search(Breakpoint {
+            remaining,
+            depth,
+            forwards,
+            aims,
+            len,
+            candidates,
+            current: None,
+        })
+    }
+
+    /// Depth first search with an explicit stack, as plans may have many forward commands
+    fn search(
+        &mut self,
+        remaining: i64,
+        depth: i64,
+        forwards: usize,
+        aims: usize,
+        plan: &mut Vec<Command>,
+    ) -> bool {
+        let mut stack = match self.visit(remaining, depth, forwards, aims, plan.len()) {
+            Visit::Reached => return true,
+            Visit::Failed => return false,
+            Visit::Search(breakpoint) => vec![breakpoint],
+        };
+        while let Some(breakpoint) = stack.last_mut() {
+            plan.truncate(breakpoint.len);
+            let Some((aim, forward)) = breakpoint.next_move(self.step) else {
+                let key = (
+                    breakpoint.remaining,
+                    breakpoint.depth,
+                    breakpoint.forwards,
+                    breakpoint.aims,
+                );
+                self.failed.insert(key);
+                stack.pop();
+                continue;
+            };
+            plan.extend(aim_commands(aim, self.step));
+            plan.push(Command::Forward(forward as i32));
+            let visit = self.visit(
+                breakpoint.remaining - forward,
+                breakpoint.depth - aim * breakpoint.remaining,
+                breakpoint.forwards - 1,
+                breakpoint.aims - self.cost(aim),
+                plan.len(),
+            );
+            match visit {
+                Visit::Reached => return true,
+                Visit::Failed => {}
+                Visit::Search(next) => stack.push(next),
+            }
+        }
+        false
+    }
+}
+
+/// Commands changing the aim by `aim` with steps of at most `step`
+fn aim_commands(aim: i64, step: i64) -> impl Iterator<Item = Command> {
+    let full = aim.abs() / step;
+    let steps =
+        std::iter::repeat_n(step, full as usize).chain(Some(aim.abs() % step).filter(|s| *s > 0));
+    steps.map(move |s| {
+        if aim > 0 {
+            Command::Down(s as i32)
+        } else {
+            Command::Up(s as i32)
+        }
+    })
+}
+
+/// Shortest plan of forward, down and up commands, which brings the aim based model from the
+/// start to the horizontal position `x` and `depth`
+///
+/// Plans never move back, which keeps them to the commands of the puzzle, so `verify_plan`
+/// rejects `back` as well. Every plan needs at least `x / max_step` forward commands and
+/// `depth / (x * max_step)` aim commands, both rounded up. The number of commands is increased
+/// from there, until a plan exists. Moving forward with aim 0 and changing the aim by `depth`
+/// before the last step always works, which bounds the search. Plans below `min_commands` are
+/// then padded with alternating `down 1` and `up 1`, which only change the final aim. Fails if
+/// `x` is negative or 0 with a depth other than 0.
+pub fn plan(x: i32, depth: i32, constraints: Constraints) -> Result<Vec<Command>, PlanError> {
+    let step = constraints.max_step as i64;
+    if step <= 0 {
+        return Err(PlanError::InvalidStep(constraints.max_step));
+    }
+    let unreachable = PlanError::Unreachable { x, depth };
+    let (x, depth) = (x as i64, depth as i64);
+    if x < 0 || (x == 0 && depth != 0) {
+        return Err(unreachable);
+    }
+    let forwards = (x + step - 1) / step;
+    let aims = if x == 0 {
+        0
+    } else {
+        (depth.abs() + x * step - 1) / (x * step)
+    };
+    let longest = if x == 0 {
+        0
+    } else {
+        (x - 1 + step - 1) / step + (depth.abs() + step - 1) / step + 1
+    };
+    let mut planner = Planner {
+        step,
+        failed: HashSet::new(),
+    };
+    let mut plan = Vec::new();
+    let found = ((forwards + aims) as usize..=longest as usize).any(|total| {
+        (forwards as usize..=total)
+            .any(|forwards| planner.search(x, depth, forwards, total - forwards, &mut plan))
+    });
+    if !found {
+        return Err(unreachable);
+    }
+    let padding = constraints.min_commands.saturating_sub(plan.len());
+    plan.extend((0..padding).map(|i| {
+        if i % 2 == 0 {
+            Command::Down(1)
+        } else {
+            Command::Up(1)
+        }
+    }));
+    Ok(plan)
+}
+
+/// Runs the plan through `navigate_v2` and checks that it ends at `x` and `depth` within the
+/// constraints, using only the commands `plan` uses
+pub fn verify_plan(plan: &[Command], x: i32, depth: i32, constraints: Constraints) -> bool {
+    let within = plan.iter().all(|command| match *command {
+        Command::Up(n) | Command::Down(n) | Command::Forward(n) => {
+            (1..=constraints.max_step).contains(&n)
+        }
+        Command::Back(_) => false,
+    });
+    let position = navigate_v2(plan.iter().copied());
+    within && plan.len() >= constraints.min_commands && (position.x, position.y) == (x, depth)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let pos = navigate_reader(input.as_bytes())?;
+        Ok(Box::new(pos.x * pos.y))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let pos = navigate_reader_v2(input.as_bytes())?;
+        Ok(Box::new(pos.x * pos.y))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{fs::File, io::Cursor};
+    use crate::inputs::{read_input, Variant};
+    use std::io::Cursor;
 
     #[test]
     fn test_part1() {
@@ -109,8 +741,8 @@ mod tests {
 
     #[test]
     fn part1() {
-        let test_input = File::open("puzzleData/day2.txt").unwrap();
-        let pos = navigate_reader(test_input).unwrap();
+        let test_input = read_input(2, Variant::Real).unwrap();
+        let pos = navigate_reader(test_input.as_bytes()).unwrap();
         assert_eq!(1694130, pos.x * pos.y);
     }
 
@@ -121,10 +753,226 @@ mod tests {
         assert_eq!(900, pos.x * pos.y);
     }
 
+    #[test]
+    fn custom_model() {
+        /// Distance travelled in any direction
+        struct Distance;
+
+        impl NavigationModel for Distance {
+            type State = i32;
+
+            fn apply(&self, distance: &mut i32, command: &Command) {
+                *distance += match *command {
+                    Command::Up(n) | Command::Down(n) | Command::Forward(n) | Command::Back(n) => n,
+                };
+            }
+        }
+
+        let test_input = Cursor::new("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2");
+        assert_eq!(31, navigate_reader_with(&Distance, test_input).unwrap());
+    }
+
+    #[test]
+    fn trajectory() {
+        let test_input = Cursor::new("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2");
+        let trajectory = record_reader_with(&Aim, test_input).unwrap();
+        assert_eq!(6, trajectory.steps.len());
+        assert_eq!(
+            &navigate_reader_v2(Cursor::new(
+                "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2"
+            ))
+            .unwrap(),
+            trajectory.end()
+        );
+        let deeper = trajectory.first_deeper_than(40).unwrap();
+        assert_eq!(5, deeper.command);
+        assert_eq!(None, trajectory.first_deeper_than(60));
+        assert_eq!(
+            "command,x,depth,aim\n,0,0,0\n0,5,0,0\n1,5,0,5\n2,13,40,5\n3,13,40,2\n4,13,40,10\n5,15,60,10",
+            trajectory.to_csv()
+        );
+        let csv = record_with(&Simple, [Command::Down(2)]).to_csv();
+        assert_eq!("command,x,depth\n,0,0\n0,0,2", csv);
+    }
+
+    #[test]
+    fn svg_polyline() {
+        let trajectory = record_with(&Aim, [Command::Down(2), Command::Forward(3)]);
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 3 6\">\n\
+             <polyline fill=\"none\" stroke=\"black\" points=\"0,0 0,0 3,6\"/>\n</svg>",
+            trajectory.to_svg()
+        );
+    }
+
+    const SCRIPT: &str = "# Same course as the example
+sub dive {
+    down 5
+    forward 8 # deepest part
+}
+
+forward 5
+call dive
+repeat 2 {
+    up 3
+    down 8
+    repeat 0 {
+        back 100
+    }
+}
+forward 2
+back 1";
+
+    #[test]
+    fn expand_script() {
+        let script = parse_script(SCRIPT.as_bytes()).unwrap();
+        use Command::*;
+        let commands = script.expand().unwrap();
+        assert_eq!(
+            vec![
+                Forward(5),
+                Down(5),
+                Forward(8),
+                Up(3),
+                Down(8),
+                Up(3),
+                Down(8),
+                Forward(2),
+                Back(1)
+            ],
+            commands
+        );
+        let position = navigate_v2(commands.into_iter());
+        assert_eq!((14, 55, 15), (position.x, position.y, position.aim));
+    }
+
+    #[test]
+    fn invalid_script() {
+        let error = |script: &str| parse_script(script.as_bytes()).unwrap_err();
+        let e = error("repeat 2 {\n  down x\n}");
+        assert_eq!((2, 8), (e.line, e.column));
+        let e = error("forward 1\n  }");
+        assert_eq!((2, 3), (e.line, e.column));
+        let e = error("sub dive {\n  down 1");
+        assert_eq!((1, 11), (e.line, e.column));
+        let e = error("repeat 2 # no block");
+        assert_eq!((1, 9), (e.line, e.column));
+        let e = error("sub a {\n}\nsub a {\n}");
+        assert_eq!((3, 5), (e.line, e.column));
+        let script = parse_script("call a".as_bytes()).unwrap();
+        assert_eq!(
+            Err(ScriptError::UnknownSubroutine("a".to_owned())),
+            script.expand()
+        );
+        let script =
+            parse_script("sub a {\ncall b\n}\nsub b {\ncall a\n}\ncall a".as_bytes()).unwrap();
+        assert_eq!(Err(ScriptError::Recursion("a".to_owned())), script.expand());
+    }
+
+    #[test]
+    fn plan_example() {
+        let constraints = Constraints::default();
+        let plan = plan(15, 60, constraints).unwrap();
+        assert!(verify_plan(&plan, 15, 60, constraints));
+        assert_eq!(3, plan.len());
+        let plan = super::plan(1900, -900_000, constraints).unwrap();
+        assert!(verify_plan(&plan, 1900, -900_000, constraints));
+        assert_eq!(212 + 53, plan.len());
+        // Deeper than the call stack could go with one frame per forward command
+        let plan = super::plan(2_000_000, 4_000_000, constraints).unwrap();
+        assert!(verify_plan(&plan, 2_000_000, 4_000_000, constraints));
+        assert_eq!(222_223 + 1, plan.len());
+        let unreachable = PlanError::Unreachable { x: 0, depth: 5 };
+        assert_eq!(Err(unreachable), super::plan(0, 5, constraints));
+        let unreachable = PlanError::Unreachable { x: -1, depth: 0 };
+        assert_eq!(Err(unreachable), super::plan(-1, 0, constraints));
+        let constraints = Constraints {
+            max_step: 0,
+            min_commands: 0,
+        };
+        assert_eq!(
+            Err(PlanError::InvalidStep(0)),
+            super::plan(1, 0, constraints)
+        );
+    }
+
+    #[test]
+    fn plan_constraints() {
+        let constraints = Constraints {
+            max_step: 2,
+            min_commands: 6,
+        };
+        let plan = plan(3, 3, constraints).unwrap();
+        assert_eq!(6, plan.len());
+        assert!(verify_plan(&plan, 3, 3, constraints));
+        assert!(!verify_plan(&plan[..3], 3, 3, constraints));
+        let too_large = [Command::Down(1), Command::Forward(3)];
+        assert!(!verify_plan(&too_large, 3, 3, constraints));
+        let padding = &plan[plan.len() - 2..];
+        assert!(padding
+            .iter()
+            .all(|c| matches!(c, Command::Down(1) | Command::Up(1))));
+        use Command::*;
+        let backwards = [Down(2), Forward(1), Up(2), Back(1), Down(1), Up(1)];
+        assert_eq!(2, navigate_v2(backwards.into_iter()).y);
+        assert!(!verify_plan(&backwards, 0, 2, constraints));
+        assert!(super::plan(0, 2, constraints).is_err());
+    }
+
+    #[test]
+    fn plan_is_shortest() {
+        let constraints = Constraints {
+            max_step: 2,
+            min_commands: 0,
+        };
+        let steps = 1..=constraints.max_step;
+        for x in 0..=4 {
+            for depth in -12..=12 {
+                let shortest = pathfinding::directed::bfs::bfs(
+                    &(0, 0, 0),
+                    |&(px, py, aim)| {
+                        let forwards = steps
+                            .clone()
+                            .filter(move |n| px + n <= x)
+                            .map(move |n| (px + n, py + aim * n, aim));
+                        let aims = steps
+                            .clone()
+                            .flat_map(|n| [n, -n])
+                            .filter(move |n| (aim + n).abs() <= 12)
+                            .map(move |n| (px, py, aim + n));
+                        forwards.chain(aims).collect::<Vec<_>>()
+                    },
+                    |&(px, py, _)| (px, py) == (x, depth),
+                );
+                let plan = plan(x, depth, constraints);
+                assert_eq!(
+                    shortest.map(|path| path.len() - 1),
+                    plan.as_ref().ok().map(Vec::len),
+                    "{} {}",
+                    x,
+                    depth
+                );
+                if let Ok(plan) = plan {
+                    assert!(verify_plan(&plan, x, depth, constraints));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn locate_invalid_command() {
+        let test_input = Cursor::new("forward 5\ndown 5\nforward x");
+        let error = navigate_reader(test_input).err().unwrap();
+        assert_eq!((3, 9), (error.line, error.column));
+        let test_input = Cursor::new("forward 5\nsideways 5");
+        let error = navigate_reader(test_input).err().unwrap();
+        assert_eq!((2, 1), (error.line, error.column));
+    }
+
     #[test]
     fn part2() {
-        let test_input = File::open("puzzleData/day2.txt").unwrap();
-        let pos = navigate_reader_v2(test_input).unwrap();
+        let test_input = read_input(2, Variant::Real).unwrap();
+        let pos = navigate_reader_v2(test_input.as_bytes()).unwrap();
         assert_eq!(1698850445, pos.x * pos.y);
     }
 }
@@ -0,0 +1,206 @@
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    Answer, DynError, Grid, Solution,
+};
+
+const DAY: u8 = 20;
+const ALGORITHM_LEN: usize = 512;
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+/// Image on an infinite grid, where every pixel outside of `pixels` has the `background` value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Enhancer {
+    algorithm: Vec<bool>,
+}
+
+/// Enhancement algorithm and input image, which are separated by a blank line
+pub fn parse(input: &str) -> Result<(Enhancer, Image), ParseError> {
+    let mut lines = input.lines();
+    let algorithm = lines.next().unwrap_or_default();
+    match lines.next() {
+        Some("") => {}
+        Some(line) => {
+            let kind = ParseErrorKind::Missing("blank line");
+            return Err(LineError::new(1, kind).locate(DAY, 2, line));
+        }
+        None => {
+            let kind = ParseErrorKind::Missing("image");
+            return Err(LineError::after(algorithm, kind).locate(DAY, 1, algorithm));
+        }
+    }
+    let enhancer = Enhancer::parse(algorithm)?;
+    // The image starts after the algorithm and a blank line
+    let image = lines.collect::<Vec<_>>().join("\n");
+    let pixels = Grid::parse_with(DAY, &image, pixel).map_err(|mut e| {
+        e.line += 2;
+        e
+    })?;
+    Ok((
+        enhancer,
+        Image {
+            pixels,
+            background: false,
+        },
+    ))
+}
+
+impl Enhancer {
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let algorithm = line
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                pixel(c).ok_or_else(|| {
+                    LineError::new(i + 1, ParseErrorKind::InvalidChar(c)).locate(DAY, 1, line)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if algorithm.len() != ALGORITHM_LEN {
+            return Err(LineError::new(
+                algorithm.len().min(ALGORITHM_LEN) + 1,
+                ParseErrorKind::Invalid("algorithm"),
+            )
+            .locate(DAY, 1, line));
+        }
+        Ok(Self { algorithm })
+    }
+
+    /// Grows the image by one pixel on every side, since only those can differ from the background
+    pub fn enhance(&self, image: &Image) -> Image {
+        let pixels = Grid::from_fn(
+            image.pixels.width() + 2,
+            image.pixels.height() + 2,
+            |(x, y)| {
+                let (x, y) = (x as i64 - 1, y as i64 - 1);
+                let index = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                    .map(|pos| *image.pixels.get(pos).unwrap_or(&image.background))
+                    .fold(0, |acc, lit| acc << 1 | lit as usize);
+                self.algorithm[index]
+            },
+        );
+        // Background pixels only see background pixels, so their index is all zeros or all ones
+        let all_background = if image.background {
+            ALGORITHM_LEN - 1
+        } else {
+            0
+        };
+        let background = self.algorithm[all_background];
+        Image { pixels, background }
+    }
+
+    pub fn enhance_n(&self, image: &Image, steps: usize) -> Image {
+        (0..steps).fold(image.clone(), |image, _| self.enhance(&image))
+    }
+}
+
+impl Image {
+    /// Number of lit pixels, which is None if the infinite background is lit
+    pub fn lit(&self) -> Option<usize> {
+        (!self.background).then(|| self.pixels.iter().filter(|lit| **lit).count())
+    }
+
+    /// Renders the finite part of the image with '#' for lit pixels
+    pub fn render(&self) -> String {
+        self.pixels.render(|lit| if *lit { '#' } else { '.' })
+    }
+}
+
+fn count_lit_after(input: &str, steps: usize) -> Result<usize, DynError> {
+    let (enhancer, image) = parse(input)?;
+    Ok(enhancer
+        .enhance_n(&image, steps)
+        .lit()
+        .ok_or("Infinitely many pixels are lit")?)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_lit_after(input, 2)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_lit_after(input, 50)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{read_input, Variant};
+
+    /// Algorithm as text, which lights a pixel if `f` returns true for its 3x3 index
+    fn algorithm(f: impl Fn(usize) -> bool) -> String {
+        (0..ALGORITHM_LEN)
+            .map(|i| if f(i) { '#' } else { '.' })
+            .collect()
+    }
+
+    #[test]
+    fn grow_single_pixel() {
+        let input = format!("{}\n\n...\n.#.\n...", algorithm(|i| i != 0));
+        let (enhancer, image) = parse(&input).unwrap();
+        let image = enhancer.enhance(&image);
+        assert_eq!(Some(9), image.lit());
+        assert_eq!(".....\n.###.\n.###.\n.###.\n.....", image.render());
+        assert_eq!(Some(25), enhancer.enhance(&image).lit());
+    }
+
+    #[test]
+    fn flipping_background() {
+        // Inverts every pixel, including the infinite background
+        let input = format!("{}\n\n#..\n.#.", algorithm(|i| i & 16 == 0));
+        let (enhancer, image) = parse(&input).unwrap();
+        assert_eq!(None, enhancer.enhance(&image).lit());
+        let twice = enhancer.enhance_n(&image, 2);
+        assert_eq!(Some(2), twice.lit());
+        assert_eq!(
+            ".......\n.......\n..#....\n...#...\n.......\n.......",
+            twice.render()
+        );
+        assert!(count_lit_after(&input, 1).is_err());
+        assert_eq!(2, count_lit_after(&input, 50).unwrap());
+    }
+
+    #[test]
+    fn locate_errors() {
+        let error = parse(&format!("{}\n\n..\n.x", algorithm(|_| false))).unwrap_err();
+        assert_eq!((4, 2), (error.line, error.column));
+        let error = parse("#.#\n\n..").unwrap_err();
+        assert_eq!((1, 4), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Invalid("algorithm")));
+        let error = parse(&format!("{}\n..\n.#", algorithm(|_| false))).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        let error = parse("#.#").unwrap_err();
+        assert_eq!((1, 4), (error.line, error.column));
+    }
+
+    #[test]
+    fn example() {
+        let input = read_input(20, Variant::Example).unwrap();
+        assert_eq!(35, count_lit_after(&input, 2).unwrap());
+        assert_eq!(3351, count_lit_after(&input, 50).unwrap());
+        let crlf = input.replace('\n', "\r\n");
+        assert_eq!(35, count_lit_after(&crlf, 2).unwrap());
+    }
+}
@@ -0,0 +1,266 @@
+use std::{collections::HashMap, io::Read, num::NonZeroU32};
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 21;
+/// Spaces of the board of both games of the puzzle
+const BOARD: NonZeroU32 = NonZeroU32::new(10).unwrap();
+/// Sides of the die of the deterministic game
+const DETERMINISTIC_SIDES: NonZeroU32 = NonZeroU32::new(100).unwrap();
+/// Sides of the die of the quantum game
+const QUANTUM_SIDES: NonZeroU32 = NonZeroU32::new(3).unwrap();
+
+fn parse(r: impl Read) -> Result<[u32; 2], ParseError> {
+    let positions = parse_lines(DAY, r, |line| {
+        let (_, position) = line
+            .split_once("starting position: ")
+            .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing("starting position")))?;
+        let value = position
+            .parse()
+            .map_err(|e| LineError::at(line, position, e))?;
+        if !(1..=BOARD.get()).contains(&value) {
+            let kind = ParseErrorKind::Invalid("starting position");
+            return Err(LineError::at(line, position, kind));
+        }
+        Ok(value)
+    })
+    .collect::<Result<Vec<u32>, _>>()?;
+    match positions[..] {
+        [first, second] => Ok([first, second]),
+        _ => Err(
+            LineError::new(1, ParseErrorKind::Invalid("number of players")).locate(
+                DAY,
+                positions.len().min(2) + 1,
+                "",
+            ),
+        ),
+    }
+}
+
+/// Die, which is rolled by the deterministic game
+pub trait Die {
+    fn roll(&mut self) -> u32;
+}
+
+/// Die rolling 1, 2, .. up to its number of sides and starting over
+pub struct DeterministicDie {
+    sides: NonZeroU32,
+    next: u32,
+}
+
+impl DeterministicDie {
+    pub fn new(sides: NonZeroU32) -> Self {
+        Self { sides, next: 1 }
+    }
+}
+
+impl Die for DeterministicDie {
+    fn roll(&mut self) -> u32 {
+        let roll = self.next;
+        self.next = self.next % self.sides + 1;
+        roll
+    }
+}
+
+/// Rules of a game with a circular board of the spaces 1 to `board`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Game {
+    pub board: NonZeroU32,
+    pub rolls_per_turn: u32,
+    pub winning_score: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub scores: [u32; 2],
+    pub rolls: u32,
+}
+
+impl Outcome {
+    pub fn loser_score(&self) -> u32 {
+        self.scores[0].min(self.scores[1])
+    }
+}
+
+type Universes = HashMap<([u32; 2], [u32; 2]), [u64; 2]>;
+
+impl Game {
+    pub const DETERMINISTIC: Game = Game {
+        board: BOARD,
+        rolls_per_turn: 3,
+        winning_score: 1000,
+    };
+    pub const QUANTUM: Game = Game {
+        board: BOARD,
+        rolls_per_turn: 3,
+        winning_score: 21,
+    };
+
+    fn advance(&self, position: u32, steps: u32) -> u32 {
+        (position - 1 + steps) % self.board + 1
+    }
+
+    pub fn play(&self, start: [u32; 2], die: &mut impl Die) -> Outcome {
+        let mut positions = start;
+        let mut scores = [0; 2];
+        let mut rolls = 0;
+        for player in [0, 1].into_iter().cycle() {
+            let steps = (0..self.rolls_per_turn).map(|_| die.roll()).sum();
+            rolls += self.rolls_per_turn;
+            positions[player] = self.advance(positions[player], steps);
+            scores[player] += positions[player];
+            if scores[player] >= self.winning_score {
+                break;
+            }
+        }
+        Outcome { scores, rolls }
+    }
+
+    /// Number of universes in which each player wins,
+    /// if every roll of a die with `sides` splits the universe
+    pub fn count_universes(&self, start: [u32; 2], sides: NonZeroU32) -> [u64; 2] {
+        let mut sums = HashMap::<u32, u64>::new();
+        sums.insert(0, 1);
+        for _ in 0..self.rolls_per_turn {
+            let mut next = HashMap::new();
+            for (sum, count) in sums {
+                for roll in 1..=sides.get() {
+                    *next.entry(sum + roll).or_insert(0) += count;
+                }
+            }
+            sums = next;
+        }
+        let sums = sums.into_iter().collect::<Vec<_>>();
+        self.wins(start, [0, 0], &sums, &mut HashMap::new())
+    }
+
+    /// Wins of the player about to move first and of the other one
+    fn wins(
+        &self,
+        positions: [u32; 2],
+        scores: [u32; 2],
+        sums: &[(u32, u64)],
+        cache: &mut Universes,
+    ) -> [u64; 2] {
+        if let Some(wins) = cache.get(&(positions, scores)) {
+            return *wins;
+        }
+        let mut wins = [0, 0];
+        for &(sum, count) in sums {
+            let position = self.advance(positions[0], sum);
+            let score = scores[0] + position;
+            if score >= self.winning_score {
+                wins[0] += count;
+            } else {
+                let [other, current] =
+                    self.wins([positions[1], position], [scores[1], score], sums, cache);
+                wins[0] += count * current;
+                wins[1] += count * other;
+            }
+        }
+        cache.insert((positions, scores), wins);
+        wins
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let outcome = Game::DETERMINISTIC.play(
+            parse(input.as_bytes())?,
+            &mut DeterministicDie::new(DETERMINISTIC_SIDES),
+        );
+        Ok(Box::new(outcome.loser_score() * outcome.rolls))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let wins = Game::QUANTUM.count_universes(parse(input.as_bytes())?, QUANTUM_SIDES);
+        Ok(Box::new(wins[0].max(wins[1])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn part1_test() {
+        let start = parse(TEST_INPUT.as_bytes()).unwrap();
+        let outcome =
+            Game::DETERMINISTIC.play(start, &mut DeterministicDie::new(DETERMINISTIC_SIDES));
+        assert_eq!([1000, 745], outcome.scores);
+        assert_eq!(993, outcome.rolls);
+        assert_eq!(739785, outcome.loser_score() * outcome.rolls);
+    }
+
+    #[test]
+    fn part2_test() {
+        let start = parse(TEST_INPUT.as_bytes()).unwrap();
+        assert_eq!(
+            [444356092776315, 341960390180808],
+            Game::QUANTUM.count_universes(start, QUANTUM_SIDES)
+        );
+    }
+
+    #[test]
+    fn other_rules() {
+        // With a one sided die, player 1 reaches a score of 3 first
+        let game = Game {
+            board: BOARD,
+            rolls_per_turn: 1,
+            winning_score: 3,
+        };
+        let sides = |n| NonZeroU32::new(n).unwrap();
+        assert_eq!([1, 0], game.count_universes([1, 1], sides(1)));
+        assert_eq!([3, 1], game.count_universes([1, 1], sides(2)));
+    }
+
+    #[test]
+    fn smallest_board_and_die() {
+        // Empty boards and dice without sides can't be built, the smallest ones only score 1
+        assert!(NonZeroU32::new(0).is_none());
+        let one = NonZeroU32::new(1).unwrap();
+        let game = Game {
+            board: one,
+            rolls_per_turn: 3,
+            winning_score: 5,
+        };
+        let outcome = game.play([1, 1], &mut DeterministicDie::new(one));
+        assert_eq!(([5, 4], 27), (outcome.scores, outcome.rolls));
+        assert_eq!([1, 0], game.count_universes([1, 1], one));
+    }
+
+    #[test]
+    fn locate_missing_player() {
+        let error = parse("Player 1 starting position: 4".as_bytes()).unwrap_err();
+        assert_eq!(2, error.line);
+        let error = parse("Player 1 starting position: x".as_bytes()).unwrap_err();
+        assert_eq!((1, 29), (error.line, error.column));
+    }
+
+    #[test]
+    fn reject_position_outside_board() {
+        for position in ["0", "11"] {
+            let input = format!(
+                "Player 1 starting position: 4\nPlayer 2 starting position: {}",
+                position
+            );
+            let error = parse(input.as_bytes()).unwrap_err();
+            assert_eq!((2, 29), (error.line, error.column));
+            assert!(matches!(error.kind, ParseErrorKind::Invalid(_)));
+        }
+    }
+}
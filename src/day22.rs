@@ -0,0 +1,261 @@
+use std::{collections::HashMap, io::Read};
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 22;
+
+/// Axis aligned box of all cubes from `min` to `max`, both inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+impl Cuboid {
+    pub const INITIALIZATION: Cuboid = Cuboid {
+        min: [-50; 3],
+        max: [50; 3],
+    };
+
+    pub fn volume(&self) -> i64 {
+        (0..3).map(|i| self.max[i] - self.min[i] + 1).product()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = [0, 1, 2].map(|i| self.min[i].max(other.min[i]));
+        let max = [0, 1, 2].map(|i| self.max[i].min(other.max[i]));
+        (0..3)
+            .all(|i| min[i] <= max[i])
+            .then_some(Cuboid { min, max })
+    }
+
+    pub fn contains(&self, point: [i64; 3]) -> bool {
+        (0..3).all(|i| (self.min[i]..=self.max[i]).contains(&point[i]))
+    }
+}
+
+/// Set of cubes, stored as cuboids which are added or subtracted
+///
+/// Adding or removing a cuboid subtracts its intersection with every stored cuboid,
+/// so overlapping cubes are counted exactly once.
+#[derive(Debug, Clone, Default)]
+pub struct CuboidSet {
+    cuboids: HashMap<Cuboid, i64>,
+}
+
+impl CuboidSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, cuboid: Cuboid) {
+        self.subtract(&cuboid);
+        *self.cuboids.entry(cuboid).or_insert(0) += 1;
+    }
+
+    pub fn remove(&mut self, cuboid: Cuboid) {
+        self.subtract(&cuboid);
+    }
+
+    fn subtract(&mut self, cuboid: &Cuboid) {
+        let overlaps = self
+            .cuboids
+            .iter()
+            .filter_map(|(c, sign)| Some((c.intersection(cuboid)?, *sign)))
+            .collect::<Vec<_>>();
+        for (overlap, sign) in overlaps {
+            *self.cuboids.entry(overlap).or_insert(0) -= sign;
+        }
+        self.cuboids.retain(|_, sign| *sign != 0);
+    }
+
+    /// Number of cubes within the set
+    pub fn volume(&self) -> i64 {
+        self.cuboids.iter().map(|(c, sign)| c.volume() * sign).sum()
+    }
+
+    /// Number of cubes within the set and the `region`
+    pub fn volume_within(&self, region: &Cuboid) -> i64 {
+        self.cuboids
+            .iter()
+            .filter_map(|(c, sign)| Some(c.intersection(region)?.volume() * sign))
+            .sum()
+    }
+
+    pub fn contains(&self, point: [i64; 3]) -> bool {
+        self.cuboids
+            .iter()
+            .filter(|(c, _)| c.contains(point))
+            .map(|(_, sign)| sign)
+            .sum::<i64>()
+            > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+fn parse(r: impl Read) -> impl Iterator<Item = Result<Step, ParseError>> {
+    parse_lines(DAY, r, parse_step)
+}
+
+fn parse_step(line: &str) -> Result<Step, LineError> {
+    let (state, ranges) = line
+        .split_once(' ')
+        .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing("ranges")))?;
+    let on = match state {
+        "on" => true,
+        "off" => false,
+        _ => {
+            return Err(LineError::new(
+                1,
+                ParseErrorKind::UnknownCommand(state.to_owned()),
+            ))
+        }
+    };
+    let mut ranges = ranges.split(',');
+    let mut cuboid = Cuboid {
+        min: [0; 3],
+        max: [0; 3],
+    };
+    for (i, axis) in ["x=", "y=", "z="].into_iter().enumerate() {
+        let range = ranges
+            .next()
+            .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing(axis)))?;
+        let values = range
+            .strip_prefix(axis)
+            .ok_or_else(|| LineError::at(line, range, ParseErrorKind::Missing(axis)))?;
+        let (from, to) = values.split_once("..").ok_or_else(|| {
+            LineError::at(line, &values[values.len()..], ParseErrorKind::Missing(".."))
+        })?;
+        let parse = |value: &str| value.parse().map_err(|e| LineError::at(line, value, e));
+        let (from, to): (i64, i64) = (parse(from)?, parse(to)?);
+        cuboid.min[i] = from.min(to);
+        cuboid.max[i] = from.max(to);
+    }
+    if let Some(extra) = ranges.next() {
+        return Err(LineError::at(
+            line,
+            extra,
+            ParseErrorKind::Unexpected("range"),
+        ));
+    }
+    Ok(Step { on, cuboid })
+}
+
+fn reboot(r: impl Read) -> Result<CuboidSet, ParseError> {
+    let mut set = CuboidSet::new();
+    for step in parse(r) {
+        let step = step?;
+        if step.on {
+            set.insert(step.cuboid);
+        } else {
+            set.remove(step.cuboid);
+        }
+    }
+    Ok(set)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(
+            reboot(input.as_bytes())?.volume_within(&Cuboid::INITIALIZATION),
+        ))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(reboot(input.as_bytes())?.volume()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{
+        inputs::{read_input, Variant},
+        utils::pseudo_random,
+    };
+
+    const TEST_INPUT: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+    #[test]
+    fn part1_test() {
+        let set = reboot(TEST_INPUT.as_bytes()).unwrap();
+        assert_eq!(39, set.volume_within(&Cuboid::INITIALIZATION));
+        assert!(set.contains([10, 10, 10]));
+        assert!(!set.contains([11, 10, 10]));
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = read_input(22, Variant::Example).unwrap();
+        let set = reboot(input.as_bytes()).unwrap();
+        assert_eq!(474140, set.volume_within(&Cuboid::INITIALIZATION));
+        assert_eq!(2758514936282235, set.volume());
+    }
+
+    #[test]
+    fn matches_voxels() {
+        let mut random = pseudo_random(22);
+        let mut next = move |n| random(n) as i64;
+        let mut set = CuboidSet::new();
+        let mut voxels = HashSet::new();
+        for _ in 0..40 {
+            let min = [0, 1, 2].map(|_| next(8) - 4);
+            let max = min.map(|m| m + next(5));
+            let cuboid = Cuboid { min, max };
+            let cubes = (min[0]..=max[0]).flat_map(|x| {
+                (min[1]..=max[1]).flat_map(move |y| (min[2]..=max[2]).map(move |z| [x, y, z]))
+            });
+            if next(3) > 0 {
+                set.insert(cuboid);
+                voxels.extend(cubes);
+            } else {
+                set.remove(cuboid);
+                cubes.for_each(|c| {
+                    voxels.remove(&c);
+                });
+            }
+            assert_eq!(voxels.len() as i64, set.volume());
+        }
+        let region = Cuboid {
+            min: [-2; 3],
+            max: [1; 3],
+        };
+        assert_eq!(
+            voxels.iter().filter(|v| region.contains(**v)).count() as i64,
+            set.volume_within(&region)
+        );
+    }
+
+    #[test]
+    fn locate_invalid_step() {
+        let error =
+            reboot("on x=1..2,y=1..2,z=1..2\ntoggle x=1..2,y=1..2,z=1..2".as_bytes()).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        let error = reboot("on x=1..2,y=1,z=1..2".as_bytes()).unwrap_err();
+        assert_eq!((1, 14), (error.line, error.column));
+        let error = reboot("on x=1..2,y=1..2,z=1..2,w=1..2".as_bytes()).unwrap_err();
+        assert_eq!((1, 25), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Unexpected(_)));
+    }
+}
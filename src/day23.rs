@@ -0,0 +1,360 @@
+use std::fmt::Display;
+
+use pathfinding::directed::astar::astar;
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 23;
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Amphipod {
+    const ALL: [Amphipod; ROOMS] = [Amphipod::A, Amphipod::B, Amphipod::C, Amphipod::D];
+
+    fn from_char(c: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.to_char() == c)
+    }
+
+    fn to_char(self) -> char {
+        (b'A' + self.room() as u8) as char
+    }
+
+    /// Index of the room this amphipod belongs to
+    fn room(self) -> usize {
+        self as usize
+    }
+
+    fn energy(self) -> u32 {
+        10u32.pow(self as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Hallway(usize),
+    /// Room with its depth, where 0 is next to the hallway
+    Room(usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub amphipod: Amphipod,
+    pub from: Location,
+    pub to: Location,
+    pub energy: u32,
+}
+
+/// Hallway and rooms, where rooms are stored row by row starting next to the hallway
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY],
+    rows: Vec<[Option<Amphipod>; ROOMS]>,
+}
+
+/// Hallway position right above a room, where amphipods never stop
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn is_entrance(x: usize) -> bool {
+    (0..ROOMS).any(|room| entrance(room) == x)
+}
+
+/// Burrow of the diagram, which may contain any number of room rows
+pub fn parse(input: &str) -> Result<Burrow, ParseError> {
+    let mut lines = input.lines().enumerate().skip(1);
+    let (i, hallway_line) = lines
+        .next()
+        .ok_or_else(|| LineError::new(1, ParseErrorKind::Missing("hallway")).locate(DAY, 2, ""))?;
+    let mut hallway = [None; HALLWAY];
+    for (x, cell) in hallway.iter_mut().enumerate() {
+        *cell = parse_cell(hallway_line, x + 1).map_err(|e| e.locate(DAY, i + 1, hallway_line))?;
+    }
+    let mut rows = Vec::new();
+    for (i, line) in lines {
+        if line.trim() == "#########" {
+            break;
+        }
+        let mut row = [None; ROOMS];
+        for (room, cell) in row.iter_mut().enumerate() {
+            *cell = parse_cell(line, 3 + 2 * room).map_err(|e| e.locate(DAY, i + 1, line))?;
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(LineError::new(1, ParseErrorKind::Missing("rooms")).locate(DAY, 3, ""));
+    }
+    Ok(Burrow { hallway, rows })
+}
+
+fn parse_cell(line: &str, column: usize) -> Result<Option<Amphipod>, LineError> {
+    match line.chars().nth(column) {
+        Some('.') => Ok(None),
+        Some(c) => Amphipod::from_char(c)
+            .map(Some)
+            .ok_or_else(|| LineError::new(column + 1, ParseErrorKind::InvalidChar(c))),
+        None => Err(LineError::after(line, ParseErrorKind::Missing("cell"))),
+    }
+}
+
+impl Burrow {
+    /// Inserts the two rows of the folded part of the diagram below the first row
+    pub fn unfold(&self) -> Burrow {
+        use Amphipod::*;
+        let mut rows = self.rows.clone();
+        rows.splice(
+            1..1,
+            [
+                [Some(D), Some(C), Some(B), Some(A)],
+                [Some(D), Some(B), Some(A), Some(C)],
+            ],
+        );
+        Burrow {
+            hallway: self.hallway,
+            rows,
+        }
+    }
+
+    fn get(&self, location: Location) -> Option<Amphipod> {
+        match location {
+            Location::Hallway(x) => self.hallway[x],
+            Location::Room(room, depth) => self.rows[depth][room],
+        }
+    }
+
+    fn set(&mut self, location: Location, amphipod: Option<Amphipod>) {
+        match location {
+            Location::Hallway(x) => self.hallway[x] = amphipod,
+            Location::Room(room, depth) => self.rows[depth][room] = amphipod,
+        }
+    }
+
+    fn is_organized(&self) -> bool {
+        self.hallway.iter().all(Option::is_none)
+            && self.rows.iter().all(|row| {
+                row.iter()
+                    .zip(Amphipod::ALL)
+                    .all(|(cell, a)| *cell == Some(a))
+            })
+    }
+
+    /// True if the room contains only amphipods, which belong to it
+    fn accepts(&self, room: usize) -> bool {
+        self.rows
+            .iter()
+            .all(|row| row[room].is_none_or(|a| a.room() == room))
+    }
+
+    fn hallway_is_free(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    fn moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        // Into the own room, which is always at least as good as any other move
+        for (x, amphipod) in self.hallway.iter().enumerate() {
+            let Some(amphipod) = *amphipod else { continue };
+            let room = amphipod.room();
+            if !self.accepts(room) || !self.hallway_is_free(x, entrance(room)) {
+                continue;
+            }
+            let depth = self.rows.iter().rposition(|row| row[room].is_none());
+            if let Some(depth) = depth {
+                return vec![self.build_move(Location::Hallway(x), Location::Room(room, depth))];
+            }
+        }
+        // Out of rooms, which still contain amphipods of other rooms
+        for room in (0..ROOMS).filter(|room| !self.accepts(*room)) {
+            let Some(depth) = self.rows.iter().position(|row| row[room].is_some()) else {
+                continue;
+            };
+            let from = Location::Room(room, depth);
+            let free = (0..HALLWAY)
+                .filter(|x| !is_entrance(*x) && self.hallway_is_free(entrance(room), *x));
+            moves.extend(free.map(|x| self.build_move(from, Location::Hallway(x))));
+        }
+        moves
+    }
+
+    fn build_move(&self, from: Location, to: Location) -> Move {
+        let amphipod = self.get(from).expect("moves start at an amphipod");
+        let steps = |location| match location {
+            Location::Hallway(x) => (x, 0),
+            Location::Room(room, depth) => (entrance(room), depth + 1),
+        };
+        let ((from_x, from_depth), (to_x, to_depth)) = (steps(from), steps(to));
+        let distance = from_x.abs_diff(to_x) + from_depth + to_depth;
+        Move {
+            amphipod,
+            from,
+            to,
+            energy: distance as u32 * amphipod.energy(),
+        }
+    }
+
+    fn apply(&self, m: &Move) -> Burrow {
+        let mut next = self.clone();
+        next.set(m.from, None);
+        next.set(m.to, Some(m.amphipod));
+        next
+    }
+
+    /// Lower bound of the energy left, which ignores blocking amphipods
+    fn heuristic(&self) -> u32 {
+        let in_hallway = self.hallway.iter().enumerate().filter_map(|(x, a)| {
+            let a = (*a)?;
+            Some((x.abs_diff(entrance(a.room())) + 1) as u32 * a.energy())
+        });
+        let in_rooms = self.rows.iter().enumerate().flat_map(|(depth, row)| {
+            row.iter().enumerate().filter_map(move |(room, a)| {
+                let a = (*a)?;
+                let below_settled = self.rows[depth..].iter().all(|r| r[room] == Some(a));
+                let distance = if a.room() != room {
+                    depth + 1 + entrance(room).abs_diff(entrance(a.room())) + 1
+                } else if !below_settled {
+                    // Up to the hallway, one step aside, back and into the room
+                    depth + 4
+                } else {
+                    0
+                };
+                Some(distance as u32 * a.energy())
+            })
+        });
+        in_hallway.chain(in_rooms).sum()
+    }
+
+    /// Cheapest moves to organize all amphipods into their rooms and their total energy
+    pub fn organize(&self) -> Option<(Vec<Move>, u32)> {
+        let (states, energy) = astar(
+            self,
+            |burrow| {
+                burrow
+                    .moves()
+                    .into_iter()
+                    .map(|m| (burrow.apply(&m), m.energy))
+                    .collect::<Vec<_>>()
+            },
+            Burrow::heuristic,
+            Burrow::is_organized,
+        )?;
+        let moves = states
+            .windows(2)
+            .map(|pair| pair[0].move_to(&pair[1]))
+            .collect();
+        Some((moves, energy))
+    }
+
+    /// Single move, which turns this burrow into `next`
+    fn move_to(&self, next: &Burrow) -> Move {
+        let locations = (0..HALLWAY).map(Location::Hallway).chain(
+            (0..self.rows.len())
+                .flat_map(|depth| (0..ROOMS).map(move |room| Location::Room(room, depth))),
+        );
+        let (mut from, mut to) = (None, None);
+        for location in locations {
+            match (self.get(location), next.get(location)) {
+                (Some(_), None) => from = Some(location),
+                (None, Some(_)) => to = Some(location),
+                _ => {}
+            }
+        }
+        self.build_move(
+            from.expect("an amphipod left"),
+            to.expect("an amphipod arrived"),
+        )
+    }
+}
+
+impl Display for Burrow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cell = |a: Option<Amphipod>| a.map_or('.', Amphipod::to_char);
+        writeln!(f, "{}", "#".repeat(HALLWAY + 2))?;
+        writeln!(f, "#{}#", self.hallway.map(cell).iter().collect::<String>())?;
+        for (depth, row) in self.rows.iter().enumerate() {
+            write!(f, "{}", if depth == 0 { "##" } else { "  " })?;
+            for a in row {
+                write!(f, "#{}", cell(*a))?;
+            }
+            writeln!(f, "{}", if depth == 0 { "###" } else { "#" })?;
+        }
+        write!(f, "  {}", "#".repeat(2 * ROOMS + 1))
+    }
+}
+
+fn minimal_energy(burrow: &Burrow) -> Result<u32, DynError> {
+    let (_, energy) = burrow.organize().ok_or("Amphipods can't be organized")?;
+    Ok(energy)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(minimal_energy(&parse(input)?)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(minimal_energy(&parse(input)?.unfold())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn display_round_trip() {
+        assert_eq!(TEST_INPUT, parse(TEST_INPUT).unwrap().to_string());
+        assert_eq!(4, parse(TEST_INPUT).unwrap().unfold().rows.len());
+    }
+
+    #[test]
+    fn part1_test() {
+        let burrow = parse(TEST_INPUT).unwrap();
+        let (moves, energy) = burrow.organize().unwrap();
+        assert_eq!(12521, energy);
+        assert_eq!(energy, moves.iter().map(|m| m.energy).sum::<u32>());
+        let end = moves.iter().fold(burrow, |burrow, m| burrow.apply(m));
+        assert!(end.is_organized());
+    }
+
+    #[test]
+    fn part2_test() {
+        let burrow = parse(TEST_INPUT).unwrap().unfold();
+        assert_eq!(44169, minimal_energy(&burrow).unwrap());
+    }
+
+    #[test]
+    fn locate_invalid_amphipod() {
+        let input = TEST_INPUT.replace("#A#D#C#A#", "#A#D#E#A#");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((4, 8), (error.line, error.column));
+    }
+}
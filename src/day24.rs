@@ -0,0 +1,399 @@
+use std::{io::Read, str::FromStr};
+
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Value(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum AluError {
+    #[error("Instruction {0} reads past the end of the input")]
+    MissingInput(usize),
+    #[error("Instruction {0} divides by zero")]
+    DivisionByZero(usize),
+    #[error("Instruction {0} takes the modulo of a negative number or by a non positive one")]
+    InvalidModulo(usize),
+    #[error("Instruction {0} overflows")]
+    Overflow(usize),
+}
+
+impl FromStr for Register {
+    type Err = ParseErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "w" => Ok(Register::W),
+            "x" => Ok(Register::X),
+            "y" => Ok(Register::Y),
+            "z" => Ok(Register::Z),
+            _ => Err(ParseErrorKind::Invalid("register")),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(register) => Ok(Operand::Register(register)),
+            Err(_) => Ok(Operand::Value(s.parse()?)),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(' ');
+        let mut next = |name| {
+            let part = parts
+                .next()
+                .ok_or_else(|| LineError::after(s, ParseErrorKind::Missing(name)))?;
+            Ok::<_, LineError>(part)
+        };
+        let op = next("instruction")?;
+        let a = next("register")?;
+        let a = a.parse().map_err(|e| LineError::at(s, a, e))?;
+        if op == "inp" {
+            return Ok(Instruction::Inp(a));
+        }
+        let b = next("operand")?;
+        let b = b.parse().map_err(|e| LineError::at(s, b, e))?;
+        match op {
+            "add" => Ok(Instruction::Add(a, b)),
+            "mul" => Ok(Instruction::Mul(a, b)),
+            "div" => Ok(Instruction::Div(a, b)),
+            "mod" => Ok(Instruction::Mod(a, b)),
+            "eql" => Ok(Instruction::Eql(a, b)),
+            _ => Err(LineError::new(
+                1,
+                ParseErrorKind::UnknownCommand(op.to_owned()),
+            )),
+        }
+    }
+}
+
+pub fn parse_program(r: impl Read) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(DAY, r, str::parse).collect()
+}
+
+/// Arithmetic logic unit with the registers w, x, y and z, which all start at 0
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register as usize]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Value(value) => value,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        program: &[Instruction],
+        inputs: impl IntoIterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let mut inputs = inputs.into_iter();
+        for (i, instruction) in program.iter().enumerate() {
+            let (a, result) = match *instruction {
+                Instruction::Inp(a) => (a, inputs.next().ok_or(AluError::MissingInput(i))?),
+                Instruction::Add(a, b) => (
+                    a,
+                    self.get(a)
+                        .checked_add(self.value(b))
+                        .ok_or(AluError::Overflow(i))?,
+                ),
+                Instruction::Mul(a, b) => (
+                    a,
+                    self.get(a)
+                        .checked_mul(self.value(b))
+                        .ok_or(AluError::Overflow(i))?,
+                ),
+                Instruction::Div(a, b) => {
+                    let divisor = self.value(b);
+                    if divisor == 0 {
+                        return Err(AluError::DivisionByZero(i));
+                    }
+                    // Only i64::MIN / -1 is left to overflow
+                    (
+                        a,
+                        self.get(a)
+                            .checked_div(divisor)
+                            .ok_or(AluError::Overflow(i))?,
+                    )
+                }
+                Instruction::Mod(a, b) => {
+                    let (value, modulus) = (self.get(a), self.value(b));
+                    if value < 0 || modulus <= 0 {
+                        return Err(AluError::InvalidModulo(i));
+                    }
+                    (a, value % modulus)
+                }
+                Instruction::Eql(a, b) => (a, (self.get(a) == self.value(b)) as i64),
+            };
+            self.registers[a as usize] = result;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of one of the 14 blocks of MONAD, which all read a digit
+/// and only differ by three constants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    div: i64,
+    check: i64,
+    offset: i64,
+}
+
+/// Every block of MONAD follows this template, where the values of `div z`, `add x` and the
+/// second `add y` differ
+const BLOCK_TEMPLATE: &str = "inp w
+mul x 0
+add x z
+mod x 26
+div z 0
+add x 0
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 0
+mul y x
+add z y";
+const DIV: usize = 4;
+const CHECK: usize = 5;
+const OFFSET: usize = 15;
+
+#[derive(thiserror::Error, Debug)]
+pub enum MonadError {
+    #[error("Block {0} of the program doesn't follow the structure of MONAD")]
+    UnexpectedBlock(usize),
+}
+
+fn monad_blocks(program: &[Instruction]) -> Result<Vec<Block>, MonadError> {
+    let template = parse_program(BLOCK_TEMPLATE.as_bytes()).expect("template is valid");
+    if !program.len().is_multiple_of(template.len()) {
+        return Err(MonadError::UnexpectedBlock(program.len() / template.len()));
+    }
+    program
+        .chunks(template.len())
+        .enumerate()
+        .map(|(i, block)| {
+            let constant = |index| match (template[index], block[index]) {
+                (Instruction::Div(a, _), Instruction::Div(b, Operand::Value(value)))
+                | (Instruction::Add(a, _), Instruction::Add(b, Operand::Value(value)))
+                    if a == b =>
+                {
+                    Some(value)
+                }
+                _ => None,
+            };
+            let matches_template = (0..template.len())
+                .filter(|index| ![DIV, CHECK, OFFSET].contains(index))
+                .all(|index| template[index] == block[index]);
+            match (constant(DIV), constant(CHECK), constant(OFFSET)) {
+                (Some(div), Some(check), Some(offset)) if matches_template => {
+                    Ok(Block { div, check, offset })
+                }
+                _ => Err(MonadError::UnexpectedBlock(i)),
+            }
+        })
+        .collect()
+}
+
+/// Largest or smallest model number, which MONAD accepts
+///
+/// Blocks with `div z 1` push their digit plus offset onto z as a base 26 stack. Blocks with
+/// `div z 26` pop it again and only avoid pushing, if their digit equals the popped value plus
+/// their check. So z ends up as 0 if each popping digit equals its pushing digit plus the sum of
+/// offset and check. The result is confirmed by running MONAD.
+pub fn find_model_number(
+    program: &[Instruction],
+    largest: bool,
+) -> Result<Option<u64>, MonadError> {
+    let blocks = monad_blocks(program)?;
+    let mut digits = vec![0i64; blocks.len()];
+    let mut stack = Vec::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if block.div == 1 {
+            stack.push((i, block.offset));
+            continue;
+        }
+        let Some((pushed, offset)) = stack.pop() else {
+            return Ok(None);
+        };
+        let diff = offset + block.check;
+        let digit = if largest {
+            9.min(9 - diff)
+        } else {
+            1.max(1 - diff)
+        };
+        if !(1..=9).contains(&digit) || !(1..=9).contains(&(digit + diff)) {
+            return Ok(None);
+        }
+        digits[pushed] = digit;
+        digits[i] = digit + diff;
+    }
+    for (pushed, _) in stack {
+        digits[pushed] = if largest { 9 } else { 1 };
+    }
+    let mut alu = Alu::default();
+    let accepted = alu.run(program, digits.iter().copied()).is_ok() && alu.get(Register::Z) == 0;
+    Ok(accepted.then(|| digits.iter().fold(0, |acc, d| acc * 10 + *d as u64)))
+}
+
+fn model_number(input: &str, largest: bool) -> Result<u64, DynError> {
+    let program = parse_program(input.as_bytes())?;
+    Ok(find_model_number(&program, largest)?.ok_or("MONAD accepts no model number")?)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse_program(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(model_number(input, true)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(model_number(input, false)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn run(program: &str, inputs: &[i64]) -> Result<Alu, AluError> {
+        let program = parse_program(program.as_bytes()).unwrap();
+        let mut alu = Alu::default();
+        alu.run(&program, inputs.iter().copied())?;
+        Ok(alu)
+    }
+
+    #[test]
+    fn interpreter() {
+        assert_eq!(-7, run("inp x\nmul x -1", &[7]).unwrap().get(Register::X));
+        let three_times = "inp z\ninp x\nmul z 3\neql z x";
+        assert_eq!(1, run(three_times, &[2, 6]).unwrap().get(Register::Z));
+        assert_eq!(0, run(three_times, &[2, 7]).unwrap().get(Register::Z));
+        assert_eq!(Err(AluError::MissingInput(1)), run(three_times, &[2]));
+        assert_eq!(
+            Err(AluError::DivisionByZero(1)),
+            run("inp x\ndiv x y", &[2])
+        );
+        assert_eq!(Err(AluError::InvalidModulo(1)), run("inp x\nmod x 0", &[2]));
+        assert_eq!(
+            Err(AluError::Overflow(1)),
+            run("add w 999999999999\nmul w w\nmul w w", &[])
+        );
+        assert_eq!(
+            Err(AluError::Overflow(2)),
+            run("inp x\nadd x 1\nadd x 9223372036854775807", &[0])
+        );
+        assert_eq!(
+            Err(AluError::Overflow(3)),
+            run("inp x\ninp y\nadd x -1\ndiv x y", &[i64::MIN + 1, -1])
+        );
+    }
+
+    /// MONAD like program of the blocks given as (div, check, offset)
+    fn monad(blocks: &[(i64, i64, i64)]) -> Vec<Instruction> {
+        let source = blocks
+            .iter()
+            .map(|(div, check, offset)| {
+                BLOCK_TEMPLATE
+                    .replace("div z 0", &format!("div z {}", div))
+                    .replace("add x 0", &format!("add x {}", check))
+                    .replace("add y 0", &format!("add y {}", offset))
+            })
+            .join("\n");
+        parse_program(source.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn search_matches_brute_force() {
+        let program = monad(&[(1, 12, 4), (1, 11, 11), (26, -5, 0), (26, -9, 1)]);
+        let accepted = (0..4)
+            .map(|_| 1..=9i64)
+            .multi_cartesian_product()
+            .filter(|digits| {
+                let mut alu = Alu::default();
+                alu.run(&program, digits.iter().copied()).unwrap();
+                alu.get(Register::Z) == 0
+            })
+            .map(|digits| digits.iter().fold(0, |acc, d| acc * 10 + *d as u64))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            accepted.iter().max().copied(),
+            find_model_number(&program, true).unwrap()
+        );
+        assert_eq!(
+            accepted.iter().min().copied(),
+            find_model_number(&program, false).unwrap()
+        );
+        assert_eq!(Some(9394), find_model_number(&program, true).unwrap());
+    }
+
+    #[test]
+    fn unexpected_program() {
+        let program = parse_program("inp w\nadd z w".as_bytes()).unwrap();
+        assert!(find_model_number(&program, true).is_err());
+    }
+
+    #[test]
+    fn locate_invalid_instruction() {
+        let error = parse_program("inp w\nadd q 1".as_bytes()).unwrap_err();
+        assert_eq!((2, 5), (error.line, error.column));
+        let error = parse_program("inp w\nsub w 1".as_bytes()).unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::UnknownCommand(_)));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{error::ParseError, utils::first_step, Answer, DynError, Grid, Part, Solution};
+
+const DAY: u8 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl Cell {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '>' => Some(Cell::East),
+            'v' => Some(Cell::South),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<Grid<Cell>, ParseError> {
+    Grid::parse_with(DAY, input, Cell::from_char)
+}
+
+/// Moves every cucumber of the herd at once, if the cell in front of it is empty.
+/// Cucumbers leaving the grid come back at the opposite edge.
+fn move_herd(m: &mut Grid<Cell>, herd: Cell) -> bool {
+    let (width, height) = (m.width(), m.height());
+    let target = |(x, y)| match herd {
+        Cell::East => ((x + 1) % width, y),
+        _ => (x, (y + 1) % height),
+    };
+    let moving = m
+        .positions()
+        .filter(|&pos| m[pos] == herd && m[target(pos)] == Cell::Empty)
+        .collect::<Vec<_>>();
+    for &pos in &moving {
+        m[pos] = Cell::Empty;
+        m[target(pos)] = herd;
+    }
+    !moving.is_empty()
+}
+
+/// Moves the east facing herd first and the south facing one afterwards
+fn step(m: &mut Grid<Cell>) -> bool {
+    let east = move_herd(m, Cell::East);
+    let south = move_herd(m, Cell::South);
+    east || south
+}
+
+fn render(m: &Grid<Cell>) -> String {
+    m.render(|cell| cell.to_char())
+}
+
+/// First step on which no sea cucumber moves
+pub fn count_steps_until_stable(input: &str) -> Result<usize, ParseError> {
+    let mut m = parse(input)?;
+    Ok(first_step(|| !step(&mut m)))
+}
+
+/// Every frame from the initial state until the herds stop moving
+pub fn frames_until_stable(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut m = parse(input)?;
+    let mut frames = vec![render(&m)];
+    first_step(|| {
+        let moved = step(&mut m);
+        if moved {
+            frames.push(render(&m));
+        }
+        !moved
+    });
+    Ok(frames)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_steps_until_stable(input)?))
+    }
+
+    fn part2(&self, _input: &str) -> Result<Answer, DynError> {
+        Err("Day 25 has no second part".into())
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        &[Part::One]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    #[test]
+    fn only_part_one() {
+        assert_eq!(&[Part::One], Day25.parts());
+    }
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(58, count_steps_until_stable(TEST_INPUT).unwrap());
+    }
+
+    fn steps(input: &str, n: usize) -> Vec<String> {
+        let mut m = parse(input).unwrap();
+        (0..n)
+            .map(|_| {
+                step(&mut m);
+                render(&m)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frames() {
+        assert_eq!(vec!["...>>>>.>..", "...>>>.>.>."], steps("...>>>>>...", 2));
+        let frames = frames_until_stable(TEST_INPUT).unwrap();
+        assert_eq!(58, frames.len());
+        assert_eq!(TEST_INPUT, frames[0]);
+        assert_eq!(
+            vec!["..........\n.>........\n..v....v>.\n.........."],
+            steps("..........\n.>v....v..\n.......>..\n..........", 1)
+        );
+    }
+
+    #[test]
+    fn wraps_around() {
+        assert_eq!(vec![">..\nv..", "v>.\n..."], steps("..>\nv..", 2));
+    }
+}
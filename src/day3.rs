@@ -1,89 +1,280 @@
-use itertools::{process_results, FoldWhile, Itertools};
-use simple_lines::ReadExt;
-use std::{io::Read, rc::Rc};
+use itertools::{FoldWhile, Itertools};
+use std::{fmt, io::Read, ops::Mul};
 
-struct FuelStats {
-    gamma: u32,
-    epsilon: u32,
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::numbered_lines,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 3;
+
+/// Storage for the bits of a report line, where bit 0 is the rightmost one
+pub trait Bits: Clone {
+    /// Largest number of bits, which fit in, or None if there is no limit
+    const CAPACITY: Option<usize>;
+
+    /// All bits of a line with `width` bits cleared
+    fn zeros(width: usize) -> Self;
+    fn bit(&self, index: usize) -> bool;
+    fn set_bit(&mut self, index: usize);
+    /// The value, or None if it doesn't fit into an u128
+    fn to_u128(&self) -> Option<u128>;
+    fn to_bit_vec(&self) -> BitVec;
+}
+
+macro_rules! impl_bits {
+    ($($t:ty),*) => {
+        $(impl Bits for $t {
+            const CAPACITY: Option<usize> = Some(<$t>::BITS as usize);
+
+            fn zeros(_width: usize) -> Self {
+                0
+            }
+
+            fn bit(&self, index: usize) -> bool {
+                self.checked_shr(index as u32).is_some_and(|bits| bits & 1 == 1)
+            }
+
+            fn set_bit(&mut self, index: usize) {
+                *self |= 1 << index;
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                Some(u128::from(*self))
+            }
+
+            fn to_bit_vec(&self) -> BitVec {
+                BitVec::from_u128(u128::from(*self))
+            }
+        })*
+    };
+}
+
+impl_bits!(u32, u64, u128);
+
+/// Bits of any number, stored in words of 64 bits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitVec {
+    width: usize,
+    words: Vec<u64>,
 }
 
-struct LifeStats {
-    co2: u32,
-    oxygen: u32,
+impl BitVec {
+    fn from_u128(value: u128) -> Self {
+        BitVec {
+            width: (u128::BITS - value.leading_zeros()) as usize,
+            words: vec![value as u64, (value >> 64) as u64],
+        }
+    }
+
+    /// Number of bits, including leading zeros
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The value in decimal digits
+    pub fn to_decimal(&self) -> String {
+        // Largest power of 10, which fits into a word
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+        let mut words = self.words.clone();
+        let mut chunks = Vec::new();
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        while !words.is_empty() {
+            let mut rest = 0;
+            for word in words.iter_mut().rev() {
+                let value = rest << 64 | u128::from(*word);
+                *word = (value / CHUNK) as u64;
+                rest = value % CHUNK;
+            }
+            chunks.push(rest as u64);
+            while words.last() == Some(&0) {
+                words.pop();
+            }
+        }
+        match chunks.split_last() {
+            Some((first, rest)) => rest.iter().rev().fold(first.to_string(), |acc, chunk| {
+                format!("{}{:019}", acc, chunk)
+            }),
+            None => "0".to_owned(),
+        }
+    }
+}
+
+/// Product of any width, by long multiplication of the words
+impl Mul for &BitVec {
+    type Output = BitVec;
+
+    fn mul(self, other: &BitVec) -> BitVec {
+        let mut words = vec![0u64; self.words.len() + other.words.len()];
+        for (i, a) in self.words.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, b) in other.words.iter().enumerate() {
+                // At most (2^64 - 1)^2 + 2 * (2^64 - 1), which is 2^128 - 1
+                let value = u128::from(*a) * u128::from(*b) + u128::from(words[i + j]) + carry;
+                words[i + j] = value as u64;
+                carry = value >> 64;
+            }
+            words[i + other.words.len()] = carry as u64;
+        }
+        BitVec {
+            width: self.width + other.width,
+            words,
+        }
+    }
+}
+
+/// Binary digits, starting with the most significant bit
+impl fmt::Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (0..self.width)
+            .rev()
+            .try_for_each(|index| f.write_str(if self.bit(index) { "1" } else { "0" }))
+    }
+}
+
+impl Bits for BitVec {
+    const CAPACITY: Option<usize> = None;
+
+    fn zeros(width: usize) -> Self {
+        BitVec {
+            width,
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .is_some_and(|word| word.bit(index % 64))
+    }
+
+    fn set_bit(&mut self, index: usize) {
+        if self.words.len() <= index / 64 {
+            self.words.resize(index / 64 + 1, 0);
+        }
+        self.words[index / 64].set_bit(index % 64);
+        self.width = self.width.max(index + 1);
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        match self.words.as_slice() {
+            [] => Some(0),
+            [low] => Some(u128::from(*low)),
+            [low, high, rest @ ..] if rest.iter().all(|word| *word == 0) => {
+                Some(u128::from(*high) << 64 | u128::from(*low))
+            }
+            _ => None,
+        }
+    }
+
+    fn to_bit_vec(&self) -> BitVec {
+        self.clone()
+    }
+}
+
+pub struct FuelStats<T> {
+    pub gamma: T,
+    pub epsilon: T,
+}
+
+pub struct LifeStats<T> {
+    pub co2: T,
+    pub oxygen: T,
 }
 
 #[derive(thiserror::Error, Debug)]
 enum Error {
-    #[error("empty input")]
-    EmptyInput,
-    #[error("unknown char")]
-    UnknownChar(char),
     #[error("No Rating")]
     CalculateRatingFailed,
 }
 
-fn build_accumulator(
-    input: impl Iterator<Item = Rc<String>>,
-) -> Result<(u32, Vec<u32>), Box<dyn std::error::Error>> {
-    let mut input = input.peekable();
-    let first = input.peek().ok_or(Error::EmptyInput)?;
-    let mut accumulator = vec![0; first.len()];
-    let mut count = 0;
-
-    for line in input {
-        count += 1;
-        for (char, acc) in line.chars().zip(accumulator.iter_mut()) {
-            match char {
-                '0' => continue,
-                '1' => *acc += 1,
-                _ => return Err(Error::UnknownChar(char).into()),
-            }
+fn empty_input() -> ParseError {
+    LineError::new(1, ParseErrorKind::EmptyInput).locate(DAY, 1, "")
+}
+
+fn parse_bits<T: Bits>(line: &str, width: usize) -> Result<T, LineError> {
+    let mut bits = T::zeros(width);
+    for (column, c) in line.chars().enumerate().take(width) {
+        match c {
+            '0' => {}
+            '1' => bits.set_bit(width - 1 - column),
+            _ => return Err(LineError::new(column + 1, ParseErrorKind::InvalidChar(c))),
         }
     }
-    Ok((count, accumulator))
+    let found = line.chars().count();
+    if found != width {
+        return Err(LineError::new(
+            found.min(width) + 1,
+            ParseErrorKind::LineLength {
+                expected: width,
+                found,
+            },
+        ));
+    }
+    Ok(bits)
 }
 
-fn parse(r: impl Read) -> Result<FuelStats, Box<dyn std::error::Error>> {
-    let lines = r.lines_rc();
-    #[allow(clippy::redundant_closure)]
-    let (count, accumulator) = process_results(lines, |nrs| build_accumulator(nrs))??;
-
-    let half_count = count / 2;
-    let acc_len = accumulator.len();
-    let epsilon = accumulator
-        .into_iter()
-        .fold(0, |acc, next| (acc << 1) + (next > half_count) as u32);
-
-    Ok(FuelStats {
-        epsilon,
-        gamma: epsilon ^ !(u32::max_value() << acc_len),
-    })
+/// Lines of the report and their width, which is the same for all lines
+fn parse_report<T: Bits>(r: impl Read) -> Result<(usize, Vec<T>), ParseError> {
+    let mut lines = numbered_lines(DAY, r).peekable();
+    let width = match lines.peek() {
+        Some(Ok((_, first))) => {
+            let width = first.chars().count();
+            if let Some(capacity) = T::CAPACITY.filter(|capacity| width > *capacity) {
+                let kind = ParseErrorKind::Invalid("number of bits");
+                return Err(LineError::new(capacity + 1, kind).locate(DAY, 1, first));
+            }
+            width
+        }
+        Some(Err(_)) => 0,
+        None => return Err(empty_input()),
+    };
+    let report = lines
+        .map(|line| {
+            let (no, line) = line?;
+            parse_bits(&line, width).map_err(|e| e.locate(DAY, no, &line))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((width, report))
 }
 
-fn parse_oxygen_and_co2(r: impl Read) -> Result<LifeStats, Box<dyn std::error::Error>> {
-    let mut peekable = r.lines_rc().peekable();
-    let line_len = peekable
-        .peek()
-        .ok_or(Error::EmptyInput)?
-        .as_ref()
-        .map_err(|_| Error::EmptyInput)?
-        .len();
-    let all: Result<Vec<_>, Box<dyn std::error::Error>> =
-        peekable.map(|l| Ok(u32::from_str_radix(&l?, 2)?)).collect();
-    let data = &mut all?[..];
+/// Gamma and epsilon rate of the report
+pub fn parse<T: Bits>(r: impl Read) -> Result<FuelStats<T>, ParseError> {
+    let (width, report) = parse_report::<T>(r)?;
+    let half_count = report.len() / 2;
+    let mut epsilon = T::zeros(width);
+    let mut gamma = T::zeros(width);
+    for index in 0..width {
+        let ones = report.iter().filter(|bits| bits.bit(index)).count();
+        if ones > half_count {
+            epsilon.set_bit(index);
+        } else {
+            gamma.set_bit(index);
+        }
+    }
+    Ok(FuelStats { gamma, epsilon })
+}
 
-    let oxygen = generate_rating(line_len, data, |a, b| a > b)?;
-    let co2 = generate_rating(line_len, data, |a, b| a <= b)?;
+/// Oxygen generator and CO2 scrubber rating of the report
+pub fn parse_oxygen_and_co2<T: Bits>(
+    r: impl Read,
+) -> Result<LifeStats<T>, Box<dyn std::error::Error>> {
+    let (width, mut report) = parse_report::<T>(r)?;
+    let oxygen = generate_rating(width, &mut report, |a, b| a > b)?;
+    let co2 = generate_rating(width, &mut report, |a, b| a <= b)?;
 
     Ok(LifeStats { oxygen, co2 })
 }
 
 /// Determiner: Fn(count_zeros, count_ones) -> take_zeros?
-fn generate_rating(
+fn generate_rating<T: Bits>(
     line_len: usize,
-    data: &mut [u32],
+    data: &mut [T],
     determiner: impl Fn(usize, usize) -> bool,
-) -> Result<u32, Box<dyn std::error::Error>> {
+) -> Result<T, Box<dyn std::error::Error>> {
     let result = (0..line_len).rev().fold_while(data, |acc, shifts| {
         // dbg!("{:?}", acc.iter().map(|f| format!("{:b}", f)).collect::<Vec<_>>());
         let next = partition_by_digit(acc, shifts, &determiner);
@@ -95,62 +286,239 @@ fn generate_rating(
     });
     match result {
         FoldWhile::Continue(_) => Err(Error::CalculateRatingFailed.into()),
-        FoldWhile::Done(x) => Ok(*x
+        FoldWhile::Done(x) => Ok(x
             .first()
-            .expect("FoldWhile::Done only returns Done if slice.len() == 1")),
+            .expect("FoldWhile::Done only returns Done if slice.len() == 1")
+            .clone()),
     }
 }
 
-fn partition_by_digit<'a>(
-    data: &'a mut [u32],
+fn partition_by_digit<'a, T: Bits>(
+    data: &'a mut [T],
     shifts: usize,
     determiner: &impl Fn(usize, usize) -> bool,
-) -> &'a mut [u32] {
-    let pos = itertools::partition(data.iter_mut(), |x| ((*x >> shifts) & 1) == 0);
+) -> &'a mut [T] {
+    let pos = itertools::partition(data.iter_mut(), |x| !x.bit(shifts));
     let (a, b) = data.split_at_mut(pos);
-    if (determiner)(a.len(), b.len()) {
+    // Columns without any 0 or 1, like leading zeros of wide reports, keep all numbers
+    if (determiner)(a.len(), b.len()) && !a.is_empty() || b.is_empty() {
         a
     } else {
         b
     }
 }
 
+/// Product of two ratings in decimal digits, which can be wider than any integer
+fn product<T: Bits>(a: &T, b: &T) -> String {
+    (&a.to_bit_vec() * &b.to_bit_vec()).to_decimal()
+}
+
+fn power_consumption<T: Bits>(input: &str) -> Result<String, DynError> {
+    let stats = parse::<T>(input.as_bytes())?;
+    Ok(product(&stats.epsilon, &stats.gamma))
+}
+
+fn life_support_rating<T: Bits>(input: &str) -> Result<String, DynError> {
+    let stats = parse_oxygen_and_co2::<T>(input.as_bytes())?;
+    Ok(product(&stats.oxygen, &stats.co2))
+}
+
+/// Width of the first line, which selects the smallest type to store the report in
+fn report_width(input: &str) -> usize {
+    input.lines().next().map_or(0, |line| line.chars().count())
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse_report::<BitVec>(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let result = match report_width(input) {
+            0..=32 => power_consumption::<u32>(input),
+            33..=64 => power_consumption::<u64>(input),
+            65..=128 => power_consumption::<u128>(input),
+            _ => power_consumption::<BitVec>(input),
+        }?;
+        Ok(Box::new(result))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let result = match report_width(input) {
+            0..=32 => life_support_rating::<u32>(input),
+            33..=64 => life_support_rating::<u64>(input),
+            65..=128 => life_support_rating::<u128>(input),
+            _ => life_support_rating::<BitVec>(input),
+        }?;
+        Ok(Box::new(result))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    const TEST_DATA: &'static str =
+    use itertools::Itertools;
+
+    use super::{BitVec, Bits, Day3};
+    use crate::{
+        error::ParseErrorKind,
+        inputs::{read_input, Variant},
+        Solution,
+    };
+    const TEST_DATA: &str =
         "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
     #[test]
     fn test_data() {
         let input = std::io::Cursor::new(TEST_DATA);
-        let result = super::parse(input).unwrap();
+        let result = super::parse::<u32>(input).unwrap();
         assert_eq!(198, result.epsilon * result.gamma);
     }
 
     #[test]
     fn part1() {
-        let input = std::fs::File::open("puzzleData/day3.txt").unwrap();
-        let result = super::parse(input).unwrap();
+        let input = read_input(3, Variant::Real).unwrap();
+        let result = super::parse::<u32>(input.as_bytes()).unwrap();
         assert_eq!(4138664, result.epsilon * result.gamma);
     }
 
     #[test]
     fn test_part2() {
         let input = std::io::Cursor::new(TEST_DATA);
-        let result = super::parse_oxygen_and_co2(input).unwrap();
+        let result = super::parse_oxygen_and_co2::<u32>(input).unwrap();
         assert_eq!(23, result.oxygen);
         assert_eq!(10, result.co2);
     }
 
     #[test]
     fn part2() {
-        let input = std::fs::File::open("puzzleData/day3.txt").unwrap();
-        let result = super::parse_oxygen_and_co2(input).unwrap();
+        let input = read_input(3, Variant::Real).unwrap();
+        let result = super::parse_oxygen_and_co2::<u32>(input.as_bytes()).unwrap();
         assert_eq!(4273224, result.oxygen * result.co2);
     }
 
+    #[test]
+    fn locate_unknown_char() {
+        let input = std::io::Cursor::new("00100\n11110\n10120");
+        let error = super::parse::<u32>(input).err().unwrap();
+        assert_eq!((3, 4), (error.line, error.column));
+    }
+
+    #[test]
+    fn wide_reports() {
+        let wide = |line: &str| format!("{}{}", "0".repeat(100), line);
+        let input = TEST_DATA.lines().map(wide).join("\n");
+        let narrow = super::parse_oxygen_and_co2::<u32>(TEST_DATA.as_bytes()).unwrap();
+        let result = super::parse_oxygen_and_co2::<u128>(input.as_bytes()).unwrap();
+        assert_eq!((23, 10), (result.oxygen, result.co2));
+        let result = super::parse_oxygen_and_co2::<BitVec>(input.as_bytes()).unwrap();
+        assert!((0..105).all(|i| result.oxygen.bit(i) == narrow.oxygen.bit(i)));
+        assert!((0..105).all(|i| result.co2.bit(i) == narrow.co2.bit(i)));
+
+        let result = super::parse::<BitVec>(input.as_bytes()).unwrap();
+        assert!(result.gamma.bit(104));
+        assert_eq!(
+            (22, 9),
+            (
+                (0..5)
+                    .filter(|i| result.epsilon.bit(*i))
+                    .map(|i| 1 << i)
+                    .sum::<u32>(),
+                (0..5)
+                    .filter(|i| result.gamma.bit(*i))
+                    .map(|i| 1 << i)
+                    .sum::<u32>()
+            )
+        );
+        let error = super::parse::<u64>(input.as_bytes()).err().unwrap();
+        assert_eq!((1, 65), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Invalid(_)));
+    }
+
+    #[test]
+    fn bit_vec_width() {
+        let mut bits = BitVec::zeros(70);
+        bits.set_bit(69);
+        bits.set_bit(0);
+        assert_eq!(70, bits.width());
+        assert_eq!(format!("1{}1", "0".repeat(68)), bits.to_string());
+        assert_eq!(Some(1 << 69 | 1), bits.to_u128());
+        bits.set_bit(128);
+        assert_eq!((129, None), (bits.width(), bits.to_u128()));
+    }
+
+    #[test]
+    fn bit_vec_product() {
+        let value = |bits: &[usize]| {
+            let mut value = BitVec::zeros(0);
+            bits.iter().for_each(|bit| value.set_bit(*bit));
+            value
+        };
+        assert_eq!("0", BitVec::zeros(200).to_decimal());
+        assert_eq!("0", (&value(&[3]) * &BitVec::zeros(70)).to_decimal());
+        assert_eq!(
+            "340282366920938463500268095579187314689",
+            (&value(&[64, 0]) * &value(&[64, 0])).to_decimal()
+        );
+        let all_ones = value(&(0..200).collect::<Vec<_>>());
+        let product = &all_ones * &value(&[130, 2, 1, 0]);
+        assert_eq!(331, product.width());
+        assert_eq!(
+            "2187250724783011924372502227117621365364417997203025368354562979926873453663177490681562999797317625",
+            product.to_decimal()
+        );
+    }
+
+    #[test]
+    fn products_of_any_width() {
+        let wide = |zeros: usize| {
+            TEST_DATA
+                .lines()
+                .map(|line| format!("{}{}", "0".repeat(zeros), line))
+                .join("\n")
+        };
+        for zeros in [0, 40, 100, 200] {
+            let input = wide(zeros);
+            assert_eq!("230", Day3.part2(&input).unwrap().to_string());
+        }
+        // Gamma has all leading bits set
+        assert_eq!(
+            ((1u128 << 45) - 1 - 22) * 22,
+            Day3.part1(&wide(40)).unwrap().to_string().parse().unwrap()
+        );
+        assert_eq!(
+            "7486212072260646196194241363498900651526",
+            Day3.part1(&wide(123)).unwrap().to_string()
+        );
+        assert_eq!(
+            "1131284383158329153981541313008178472175630907623086156052168198",
+            Day3.part1(&wide(200)).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn inconsistent_line_length() {
+        let error = super::parse::<u32>("00100\n1111\n10110".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!((2, 5), (error.line, error.column));
+        assert!(matches!(
+            error.kind,
+            ParseErrorKind::LineLength {
+                expected: 5,
+                found: 4
+            }
+        ));
+        let error = super::parse_oxygen_and_co2::<u32>("00100\n111101".as_bytes())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("line length 6 instead of 5"));
+    }
+
     #[test]
     fn partition_test() {
-        let mut raw = [0b1100, 0b0101, 0b1000, 0b0000];
+        let mut raw = [0b1100u32, 0b0101, 0b1000, 0b0000];
         let partition = super::partition_by_digit(&mut raw[..], 3, &|a, b| a >= b);
         assert_eq!(2, partition.len());
     }
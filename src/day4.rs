@@ -3,41 +3,47 @@ use std::{
     io::Read,
 };
 
-use crate::utils::AocError;
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::numbered_lines,
+    Answer, DynError, Solution,
+};
 use itertools::{process_results, Itertools};
-use simple_lines::ReadExt;
 
-fn parse(r: impl Read) -> Result<Game, Box<dyn std::error::Error>> {
-    process_results(r.lines_rc(), |mut lines| {
-        let numbers: Result<Vec<i32>, Box<dyn std::error::Error>> = lines
+const DAY: u8 = 4;
+
+fn parse_numbers(line: &str, separator: char) -> Result<Vec<i32>, LineError> {
+    line.split(separator)
+        .filter(|maybe_no| !maybe_no.is_empty())
+        .map(|no| no.parse().map_err(|e| LineError::at(line, no, e)))
+        .collect()
+}
+
+fn parse(r: impl Read) -> Result<Game, ParseError> {
+    process_results(numbered_lines(DAY, r), |mut lines| {
+        let (no, first) = lines
             .next()
-            .ok_or(AocError::EmptyInput)?
-            .split(',')
-            .map(|p| Ok(p.parse()?))
-            .collect();
-        let numbers = numbers?;
+            .ok_or_else(|| LineError::new(1, ParseErrorKind::EmptyInput).locate(DAY, 1, ""))?;
+        let numbers = parse_numbers(&first, ',').map_err(|e| e.locate(DAY, no, &first))?;
 
         let boards: Result<Vec<_>, _> = lines
             .skip(1)
             .batching(|it| {
-                let numbers: Result<Vec<i32>, Box<dyn std::error::Error>> = it
-                    .take_while(|line| !line.trim().is_empty())
-                    .flat_map(|line| {
-                        line.split(' ')
-                            .filter_map(|maybe_no| {
-                                (!maybe_no.is_empty()).then(|| Ok(maybe_no.parse::<i32>()?))
-                            })
-                            .collect::<Vec<_>>()
+                let numbers: Result<Vec<i32>, ParseError> = it
+                    .take_while(|(_, line)| !line.trim().is_empty())
+                    .map(|(no, line)| {
+                        parse_numbers(&line, ' ').map_err(|e| e.locate(DAY, no, &line))
                     })
+                    .flatten_ok()
                     .collect();
                 match numbers {
-                    Ok(numbers) => (!numbers.is_empty()).then(|| Ok(Board { numbers })),
+                    Ok(numbers) => (!numbers.is_empty()).then_some(Ok(Board { numbers })),
                     Err(e) => Some(Err(e)),
                 }
             })
             .collect();
 
-        Ok((numbers, boards?).into()) as Result<_, Box<dyn std::error::Error>>
+        Ok((numbers, boards?).into())
     })?
 }
 
@@ -52,8 +58,9 @@ struct Game {
     occupancies: Vec<u8>,
 }
 
-#[derive(Debug)]
+#[derive(thiserror::Error, Debug)]
 enum Error {
+    #[error("All numbers are used without a winning board")]
     AllNumbersAreUsed,
 }
 
@@ -141,8 +148,28 @@ impl From<(Vec<i32>, Vec<Board>)> for Game {
     }
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.as_bytes())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        let (number, remainings) = parse(input.as_bytes())?.play_all()?;
+        Ok(Box::new(number * remainings.into_iter().sum::<i32>()))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        let (number, remainings) = parse(input.as_bytes())?.find_worst_board()?;
+        Ok(Box::new(number * remainings.into_iter().sum::<i32>()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::inputs::{read_input, Variant};
     use std::io::Cursor;
 
     #[test]
@@ -157,7 +184,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let puzzle = super::parse(std::fs::File::open("puzzleData/day4.txt").unwrap()).unwrap();
+        let puzzle = super::parse(read_input(4, Variant::Real).unwrap().as_bytes()).unwrap();
         let (number, remainings) = puzzle.play_all().unwrap();
         let sum: i32 = remainings.into_iter().sum();
         assert_eq!(60368, number * sum)
@@ -174,13 +201,20 @@ mod tests {
 
     #[test]
     fn part2() {
-        let puzzle = super::parse(std::fs::File::open("puzzleData/day4.txt").unwrap()).unwrap();
+        let puzzle = super::parse(read_input(4, Variant::Real).unwrap().as_bytes()).unwrap();
         let (number, remainings) = puzzle.find_worst_board().unwrap();
         let sum: i32 = remainings.into_iter().sum();
         assert_eq!(17435, number * sum)
     }
 
-    const TEST_INPUT: &'static str =
+    #[test]
+    fn locate_invalid_board_number() {
+        let input = TEST_INPUT.replacen("17 11", "17 1x", 1);
+        let error = super::parse(Cursor::new(input)).err().unwrap();
+        assert_eq!((3, 10), (error.line, error.column));
+    }
+
+    const TEST_INPUT: &str =
         "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
 22 13 17 11  0
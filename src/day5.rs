@@ -1,28 +1,39 @@
-use std::{io::Read, num::ParseIntError, str::FromStr};
+use std::{io::Read, str::FromStr};
 
 use itertools::process_results;
-use simple_lines::ReadExt;
 
-fn parse(r: impl Read) -> impl Iterator<Item = Result<Line, Box<dyn std::error::Error>>> {
-    r.lines_rc().map(|l| Ok(l?.parse()?))
+use crate::{
+    error::{ErrorPolicy, LineError, ParseError, ParseErrorKind},
+    utils::{parse_lines, parse_lines_with},
+    Answer, DynError, Grid, Solution,
+};
+
+const DAY: u8 = 5;
+
+fn parse(r: impl Read) -> impl Iterator<Item = Result<Line, ParseError>> {
+    parse_lines(DAY, r, str::parse)
 }
 
-fn count_horizontal_and_vertical_intersections(
-    r: impl Read,
-) -> Result<u32, Box<dyn std::error::Error>> {
-    process_results(parse(r), |lines| {
+fn count_horizontal_and_vertical_intersections(r: impl Read) -> Result<u32, ParseError> {
+    let parsed = parse_lines_with(DAY, r, ErrorPolicy::FailFast, str::parse::<Line>, |lines| {
         count_intersections(lines.filter(|l| l.is_horizontal_or_vertical()).collect())
-    })
+    })?;
+    Ok(parsed.result)
 }
 
-fn count_all_intersections(r: impl Read) -> Result<u32, Box<dyn std::error::Error>> {
-    process_results(parse(r), |lines| count_intersections(lines.collect()))
+fn count_all_intersections(r: impl Read) -> Result<u32, ParseError> {
+    let parsed = parse_lines_with(DAY, r, ErrorPolicy::FailFast, str::parse, |lines| {
+        count_intersections(lines.collect())
+    })?;
+    Ok(parsed.result)
 }
 
 fn count_intersections(lines: Vec<Line>) -> u32 {
+    if lines.is_empty() {
+        return 0;
+    }
     let max = get_max(lines.iter().copied());
-    let x_bound = max.x + 1;
-    let mut field = vec![0; (x_bound * (max.y + 1)) as usize];
+    let mut field = Grid::new(max.x as usize + 1, max.y as usize + 1, 0u32);
     for line in lines.into_iter() {
         let x_incr = match line.0.x.cmp(&line.1.x) {
             std::cmp::Ordering::Less => 1,
@@ -37,32 +48,24 @@ fn count_intersections(lines: Vec<Line>) -> u32 {
 
         let (mut x, mut y) = (line.0.x, line.0.y);
         loop {
-            field[(x + y * x_bound) as usize] += 1;
+            field[(x as usize, y as usize)] += 1;
             x += x_incr;
             y += y_incr;
             if x == line.1.x && y == line.1.y {
-                field[(x + y * x_bound) as usize] += 1;
+                field[(x as usize, y as usize)] += 1;
                 break;
             }
         }
     }
-    /*
-    for line in field .chunks(10) {
-        for no in line {
-            print!("{}", no);
-        }
-        print!("\n");
 
-    } */
-
-    field.into_iter().filter(|x| *x >= 2).count() as u32
+    field.iter().filter(|x| **x >= 2).count() as u32
 }
 
 fn get_max(lines: impl IntoIterator<Item = Line>) -> Point {
     lines.into_iter().fold(
         Point {
-            x: i32::min_value(),
-            y: i32::min_value(),
+            x: i32::MIN,
+            y: i32::MIN,
         },
         |acc, next| Point {
             x: acc.x.max(next.0.x).max(next.1.x),
@@ -71,20 +74,10 @@ fn get_max(lines: impl IntoIterator<Item = Line>) -> Point {
     )
 }
 
-#[derive(Debug, thiserror::Error)]
-enum ParseError {
-    #[error("invalid point format")]
-    InvalidPoint,
-    #[error("invalid line format")]
-    InvalidLine,
-    #[error("parseInt Error")]
-    ParseIntError(#[from] ParseIntError),
-}
-
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
+pub(crate) struct Point {
+    pub x: i32,
+    pub y: i32,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -97,32 +90,74 @@ impl Line {
 }
 
 impl FromStr for Line {
-    type Err = ParseError;
+    type Err = LineError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split(" -> ");
-        Ok(Line(
-            parts.next().ok_or(ParseError::InvalidLine)?.parse()?,
-            parts.next().ok_or(ParseError::InvalidLine)?.parse()?,
-        ))
+        let mut next_point = || {
+            let part = parts
+                .next()
+                .ok_or_else(|| LineError::after(s, ParseErrorKind::Missing("point")))?;
+            Point::parse_within(s, part)
+        };
+        Ok(Line(next_point()?, next_point()?))
+    }
+}
+
+impl Point {
+    /// Parses `part` of `line`, so errors point to their column within the whole line.
+    /// Coordinates are indexes into the field, so negative ones are rejected
+    fn parse_within(line: &str, part: &str) -> Result<Self, LineError> {
+        let mut parts = part.split(',');
+        let mut next_coordinate = |name| {
+            let value = parts.next().ok_or_else(|| {
+                LineError::at(line, &part[part.len()..], ParseErrorKind::Missing(name))
+            })?;
+            let coordinate: i32 = value.parse().map_err(|e| LineError::at(line, value, e))?;
+            if coordinate < 0 {
+                let kind = ParseErrorKind::Invalid("negative coordinate");
+                return Err(LineError::at(line, value, kind));
+            }
+            Ok(coordinate)
+        };
+        Ok(Point {
+            x: next_coordinate("x")?,
+            y: next_coordinate("y")?,
+        })
     }
 }
 
 impl FromStr for Point {
-    type Err = ParseError;
+    type Err = LineError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(',');
-        Ok(Point {
-            x: parts.next().ok_or(ParseError::InvalidPoint)?.parse()?,
-            y: parts.next().ok_or(ParseError::InvalidPoint)?.parse()?,
-        })
+        Point::parse_within(s, s)
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        process_results(parse(input.as_bytes()), |lines| lines.count())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_horizontal_and_vertical_intersections(
+            input.as_bytes(),
+        )?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_all_intersections(input.as_bytes())?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1_test() {
@@ -137,7 +172,7 @@ mod tests {
         assert_eq!(
             6189,
             count_horizontal_and_vertical_intersections(
-                std::fs::File::open("puzzleData/day5.txt").unwrap()
+                read_input(5, Variant::Real).unwrap().as_bytes()
             )
             .unwrap()
         )
@@ -155,11 +190,22 @@ mod tests {
     fn part2() {
         assert_eq!(
             19164,
-            count_all_intersections(std::fs::File::open("puzzleData/day5.txt").unwrap()).unwrap()
+            count_all_intersections(read_input(5, Variant::Real).unwrap().as_bytes()).unwrap()
         )
     }
 
-    const TEST_INPUT: &'static str = "0,9 -> 5,9
+    #[test]
+    fn locate_invalid_coordinate() {
+        let input = TEST_INPUT.replacen("2,2 -> 2,1", "2,2 -> 2,x", 1);
+        let error = count_all_intersections(std::io::Cursor::new(input)).unwrap_err();
+        assert_eq!((4, 10), (error.line, error.column));
+        let error = count_all_intersections(std::io::Cursor::new("0,9 -> 5")).unwrap_err();
+        assert_eq!((1, 9), (error.line, error.column));
+        let error = count_all_intersections(std::io::Cursor::new("0,9 -> -5,9")).unwrap_err();
+        assert_eq!((1, 8), (error.line, error.column));
+    }
+
+    const TEST_INPUT: &str = "0,9 -> 5,9
 8,0 -> 0,8
 9,4 -> 3,4
 2,2 -> 2,1
@@ -1,16 +1,30 @@
-fn parse(input: &str) -> Result<Simulation, Box<dyn std::error::Error>> {
-    let r: Result<Vec<u8>, Box<dyn std::error::Error>> =
-        input.split(',').map(|e| Ok(e.parse()?)).collect();
-    Ok(Simulation::new(r?))
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_number_list,
+    Answer, DynError, Solution,
+};
+
+const DAY: u8 = 6;
+
+/// Largest timer of a lanternfish, which is the one of a newborn
+const MAX_TIMER: u8 = 8;
+
+fn parse(input: &str) -> Result<Simulation, ParseError> {
+    let timers = parse_number_list::<u8>(DAY, input, ',')?;
+    if let Some((part, _)) = input.split(',').zip(&timers).find(|(_, t)| **t > MAX_TIMER) {
+        let kind = ParseErrorKind::Invalid("timer above 8");
+        return Err(LineError::at(input, part, kind).locate(DAY, 1, input));
+    }
+    Ok(Simulation::new(timers))
 }
 
 struct Simulation {
-    fishes: [u64; 9],
+    fishes: [u64; MAX_TIMER as usize + 1],
 }
 
 impl Simulation {
     fn new(state: impl IntoIterator<Item = u8>) -> Self {
-        let mut fishes = [0u64; 9];
+        let mut fishes = [0u64; MAX_TIMER as usize + 1];
 
         for time in state.into_iter() {
             fishes[time as usize] += 1;
@@ -30,20 +44,45 @@ impl Simulation {
     }
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse(input.trim())?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(parse(input.trim())?.steps(80)))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(parse(input.trim())?.steps(256)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
-    const TEST_INPUT: &'static str = "3,4,3,1,2";
-    const REAL_INPUT: &'static str = "3,1,4,2,1,1,1,1,1,1,1,4,1,4,1,2,1,1,2,1,3,4,5,1,1,4,1,3,3,1,1,1,1,3,3,1,3,3,1,5,5,1,1,3,1,1,2,1,1,1,3,1,4,3,2,1,4,3,3,1,1,1,1,5,1,4,1,1,1,4,1,4,4,1,5,1,1,4,5,1,1,2,1,1,1,4,1,2,1,1,1,1,1,1,5,1,3,1,1,4,4,1,1,5,1,2,1,1,1,1,5,1,3,1,1,1,2,2,1,4,1,3,1,4,1,2,1,1,1,1,1,3,2,5,4,4,1,3,2,1,4,1,3,1,1,1,2,1,1,5,1,2,1,1,1,2,1,4,3,1,1,1,4,1,1,1,1,1,2,2,1,1,5,1,1,3,1,2,5,5,1,4,1,1,1,1,1,2,1,1,1,1,4,5,1,1,1,1,1,1,1,1,1,3,4,4,1,1,4,1,3,4,1,5,4,2,5,1,2,1,1,1,1,1,1,4,3,2,1,1,3,2,5,2,5,5,1,3,1,2,1,1,1,1,1,1,1,1,1,3,1,1,1,3,1,4,1,4,2,1,3,4,1,1,1,2,3,1,1,1,4,1,2,5,1,2,1,5,1,1,2,1,2,1,1,1,1,4,3,4,1,5,5,4,1,1,5,2,1,3";
+    const TEST_INPUT: &str = "3,4,3,1,2";
     #[test]
     fn part1_test() {
         let mut game = parse(TEST_INPUT).unwrap();
         assert_eq!(26, game.steps(18));
     }
     #[test]
+    fn locate_invalid_timer() {
+        let error = parse("3,4,,1,2").err().unwrap();
+        assert_eq!((1, 5), (error.line, error.column));
+        let error = parse("3,9,1").err().unwrap();
+        assert_eq!((1, 3), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Invalid(_)));
+    }
+    #[test]
     fn part1() {
-        let mut game = parse(REAL_INPUT).unwrap();
+        let mut game = parse(read_input(6, Variant::Real).unwrap().trim()).unwrap();
         assert_eq!(1710166656900, game.steps(256));
     }
 }
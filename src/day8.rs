@@ -1,20 +1,61 @@
 use std::{collections::HashMap, io::Read};
 
-use itertools::process_results;
-use simple_lines::ReadExt;
+use crate::{
+    error::{LineError, ParseError, ParseErrorKind},
+    utils::parse_lines,
+    Answer, DynError, Solution,
+};
 
-fn count_unique(r: impl Read) -> Result<u32, Box<dyn std::error::Error>> {
-    Ok(process_results(r.lines_rc(), |iter| {
-        iter.map(|line| {
-            line.split(" | ")
-                .nth(1)
-                .expect("Invalid input contains no | ")
-                .split(' ')
-                .filter(|c| matches!(c.len(), 2 | 3 | 4 | 7))
-                .count() as u32
-        })
-        .sum::<u32>()
-    })?)
+const DAY: u8 = 8;
+
+/// Splits an entry into its ten unique signal patterns and the four output digits
+fn split_entry(line: &str) -> Result<(Vec<&str>, Vec<&str>), LineError> {
+    if let Some((column, c)) = line
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !matches!(c, 'a'..='g' | ' ' | '|'))
+    {
+        return Err(LineError::new(column + 1, ParseErrorKind::InvalidChar(c)));
+    }
+    let mut parts = line.split(" | ");
+    let patterns = parts.next().unwrap_or_default();
+    let output = parts
+        .next()
+        .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing("' | ' separator")))?;
+    Ok((
+        words(line, patterns, 10, "number of signal patterns")?,
+        words(line, output, 4, "number of output digits")?,
+    ))
+}
+
+/// Space separated words of `part`, which has to contain exactly `count` of them
+fn words<'a>(
+    line: &str,
+    part: &'a str,
+    count: usize,
+    name: &'static str,
+) -> Result<Vec<&'a str>, LineError> {
+    let words = part.split(' ').collect::<Vec<_>>();
+    if words.len() == count {
+        Ok(words)
+    } else {
+        Err(LineError::at(line, part, ParseErrorKind::Invalid(name)))
+    }
+}
+
+fn count_unique(r: impl Read) -> Result<u32, ParseError> {
+    parse_lines(DAY, r, |line| {
+        let (_, output) = split_entry(line)?;
+        Ok(output
+            .iter()
+            .filter(|c| matches!(c.len(), 2 | 3 | 4 | 7))
+            .count() as u32)
+    })
+    .sum()
+}
+
+fn sum_outputs(r: impl Read) -> Result<u64, ParseError> {
+    parse_lines(DAY, r, solve).sum()
 }
 
 const TILES: [u8; 10] = [
@@ -79,13 +120,14 @@ fn number_from_tiles(input: u8) -> Option<u8> {
     TILES.iter().position(|x| *x == input).map(|x| x as u8)
 }
 
-fn solve(permutations: &str, input: &str) -> u64 {
+/// Segment of every wire, or None if the patterns aren't the ten digits
+fn wiring(patterns: &[&str]) -> Option<HashMap<char, Tile>> {
     let mut map: HashMap<char, usize> = HashMap::new();
     let mut four = "";
     let mut one = "";
     let mut seven = "";
     let mut eight = "";
-    for group in permutations.split(' ') {
+    for group in patterns {
         for char in group.chars() {
             *map.entry(char).or_default() += 1;
         }
@@ -108,44 +150,66 @@ fn solve(permutations: &str, input: &str) -> u64 {
         })
         .collect::<HashMap<_, _>>();
 
-    let top_right = one.chars().find(|c| !solutions.contains_key(c)).unwrap();
+    let top_right = one.chars().find(|c| !solutions.contains_key(c))?;
     solutions.insert(top_right, Tile::TopRight);
-    let center_char = four.chars().find(|c| !solutions.contains_key(c)).unwrap();
+    let center_char = four.chars().find(|c| !solutions.contains_key(c))?;
     solutions.insert(center_char, Tile::Center);
-    let top_char = seven.chars().find(|c| !solutions.contains_key(c)).unwrap();
+    let top_char = seven.chars().find(|c| !solutions.contains_key(c))?;
     solutions.insert(top_char, Tile::Top);
-    let bottom_char = eight.chars().find(|c| !solutions.contains_key(c)).unwrap();
+    let bottom_char = eight.chars().find(|c| !solutions.contains_key(c))?;
     solutions.insert(bottom_char, Tile::Bottom);
+    Some(solutions)
+}
 
-    input
-        .split(' ')
-        .map(|digit| {
-            number_from_tiles(
-                digit
-                    .chars()
-                    .fold(0, |acc, c| acc | *solutions.get(&c).unwrap() as u8),
-            )
-            .unwrap() as u64
-        })
-        .fold(0, |acc, n| acc * 10 + n)
+/// Output value of an entry, which fails if its patterns don't decode into digits
+fn solve(line: &str) -> Result<u64, LineError> {
+    let (patterns, output) = split_entry(line)?;
+    let solutions = wiring(&patterns)
+        .ok_or_else(|| LineError::new(1, ParseErrorKind::Invalid("signal patterns")))?;
+    output.into_iter().try_fold(0, |acc, digit| {
+        let number = digit
+            .chars()
+            .try_fold(0, |acc, c| Some(acc | *solutions.get(&c)? as u8))
+            .and_then(number_from_tiles)
+            .ok_or_else(|| LineError::at(line, digit, ParseErrorKind::Invalid("output digit")))?;
+        Ok(acc * 10 + number as u64)
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        parse_lines(DAY, input.as_bytes(), solve).collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_unique(input.as_bytes())?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(sum_outputs(input.as_bytes())?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
         assert_eq!(
             504,
-            count_unique(std::fs::File::open("puzzleData/day8.txt").unwrap()).unwrap()
+            count_unique(read_input(8, Variant::Real).unwrap().as_bytes()).unwrap()
         )
     }
     #[test]
     fn part1_test() {
         assert_eq!(
             26,
-            count_unique(std::fs::File::open("puzzleData/day8_test.txt").unwrap()).unwrap()
+            count_unique(read_input(8, Variant::Example).unwrap().as_bytes()).unwrap()
         )
     }
 
@@ -153,23 +217,42 @@ mod tests {
     fn part2() {
         assert_eq!(
             1073431,
-            std::fs::File::open("puzzleData/day8.txt")
-                .unwrap()
-                .lines_rc()
-                .map(|line| {
-                    let line = line.unwrap();
-                    let mut parts = line.split(" | ");
-                    solve(parts.next().unwrap(), parts.next().unwrap())
-                })
-                .sum::<u64>()
+            sum_outputs(read_input(8, Variant::Real).unwrap().as_bytes()).unwrap()
         )
     }
 
+    #[test]
+    fn locate_missing_separator() {
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab cdfeb";
+        let error = count_unique(std::io::Cursor::new(input)).unwrap_err();
+        assert_eq!((1, 65), (error.line, error.column));
+    }
+
     #[test]
     fn test_single_line() {
-        let line = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
-        let input = "cdfeb fcadb cdfeb cdbaf";
-        assert_eq!(5353, solve(line, input));
+        let line =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        assert_eq!(5353, solve(line).unwrap());
+    }
+
+    #[test]
+    fn locate_undecodable_entry() {
+        let error = sum_outputs(std::io::Cursor::new("ab cd | ab")).unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
+        let patterns = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+        let input = format!("{} | cdfeb fcadb cdfeb", patterns);
+        let error = sum_outputs(std::io::Cursor::new(input)).unwrap_err();
+        assert_eq!((1, 62), (error.line, error.column));
+        let input = format!(
+            "{} | cdfeb fcadb cdfeb cdbaf\n{} | cdfeb fcadb cg cdbaf",
+            patterns, patterns
+        );
+        let error = sum_outputs(std::io::Cursor::new(input)).unwrap_err();
+        assert_eq!((2, 74), (error.line, error.column));
+        assert!(matches!(error.kind, ParseErrorKind::Invalid(_)));
+        let input = "acedgfb acedgfb acedgfb acedgfb acedgfb acedgfb acedgfb acedgfb acedgfb acedgfb | ab ab ab ab";
+        let error = sum_outputs(std::io::Cursor::new(input)).unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
     }
 
     #[test]
@@ -1,58 +1,78 @@
-use itertools::{iproduct, Itertools};
-use pathfinding::prelude::{bfs_reach, Matrix};
+use itertools::Itertools;
+use pathfinding::prelude::bfs_reach;
 
-fn lows(m: &Matrix<u8>) -> impl Iterator<Item = (usize, usize)> + '_ {
-    iproduct!(0..m.rows, 0..m.columns).filter(|&k| m.neighbours(k, false).all(|n| m[n] > m[k]))
+use crate::{error::ParseError, grid::Pos, Answer, DynError, Grid, Solution};
+
+const DAY: u8 = 9;
+
+fn lows(m: &Grid<u8>) -> impl Iterator<Item = Pos> + '_ {
+    m.positions()
+        .filter(|&k| m.neighbours4(k).all(|n| m[n] > m[k]))
 }
 
-fn calculate_risk_level(input: &str) -> u32 {
-    let m = input.lines().map(|c| c.bytes()).collect();
-    lows(&m).map(|k| (m[k] - b'0') as u32 + 1).sum::<u32>()
+fn calculate_risk_level(input: &str) -> Result<u32, ParseError> {
+    let m = Grid::parse_digits(DAY, input)?;
+    Ok(lows(&m).map(|k| m[k] as u32 + 1).sum::<u32>())
 }
 
-fn count_top_lake_tiles(input: &str, n: usize) -> usize {
-    let m = input.lines().map(|c| c.bytes()).collect();
-    lows(&m)
+fn count_top_lake_tiles(input: &str, n: usize) -> Result<usize, ParseError> {
+    let m = Grid::parse_digits(DAY, input)?;
+    Ok(lows(&m)
         .map(|n| {
             bfs_reach(n, |&n| {
-                m.neighbours(n, false)
-                    .filter(|&k| m[k] != b'9')
-                    .collect_vec()
+                m.neighbours4(n).filter(|&k| m[k] != 9).collect_vec()
             })
             .count()
         })
         .sorted_unstable_by(|a, b| b.cmp(a))
         .take(n)
-        .product()
+        .product())
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn parse(&self, input: &str) -> Result<(), DynError> {
+        Grid::parse_digits(DAY, input)?;
+        Ok(())
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(calculate_risk_level(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, DynError> {
+        Ok(Box::new(count_top_lake_tiles(input, 3)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::*;
+    use crate::inputs::{read_input, Variant};
 
     #[test]
     fn part1() {
-        let buf = std::fs::read_to_string("puzzleData/day9.txt").unwrap();
-        assert_eq!(458, calculate_risk_level(&buf));
+        let buf = read_input(9, Variant::Real).unwrap();
+        assert_eq!(458, calculate_risk_level(&buf).unwrap());
     }
     #[test]
     fn part1_test() {
-        assert_eq!(15, calculate_risk_level(TESTDATA));
+        assert_eq!(15, calculate_risk_level(TESTDATA).unwrap());
     }
 
     #[test]
     fn part2_test() {
-        let buf = std::fs::read_to_string("puzzleData/day9.txt").unwrap();
-        assert_eq!(1391940, count_top_lake_tiles(&buf, 3));
+        let buf = read_input(9, Variant::Real).unwrap();
+        assert_eq!(1391940, count_top_lake_tiles(&buf, 3).unwrap());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(1134, count_top_lake_tiles(TESTDATA, 3));
+        assert_eq!(1134, count_top_lake_tiles(TESTDATA, 3).unwrap());
     }
 
-    const TESTDATA: &'static str = "2199943210
+    const TESTDATA: &str = "2199943210
 3987894921
 9856789892
 8767896789
@@ -0,0 +1,127 @@
+use std::num::ParseIntError;
+
+/// Error while parsing a puzzle input, pointing to the offending line and column (both 1-based)
+#[derive(thiserror::Error, Debug)]
+#[error("day {day}, line {line}, column {column}: {kind} in '{text}'")]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParseErrorKind {
+    #[error("empty input")]
+    EmptyInput,
+    #[error("invalid row format")]
+    InvalidRowFormat,
+    #[error("missing {0}")]
+    Missing(&'static str),
+    #[error("invalid {0}")]
+    Invalid(&'static str),
+    #[error("unexpected {0}")]
+    Unexpected(&'static str),
+    #[error("invalid number ({0})")]
+    InvalidNumber(#[from] ParseIntError),
+    #[error("invalid char '{0}'")]
+    InvalidChar(char),
+    #[error("unknown command '{0}'")]
+    UnknownCommand(String),
+    #[error("line length {found} instead of {expected}")]
+    LineLength { expected: usize, found: usize },
+    #[error("unreadable line ({0})")]
+    Unreadable(String),
+}
+
+/// Error within a single line, which doesn't know yet where the line is located in the input
+#[derive(Debug)]
+pub struct LineError {
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl LineError {
+    pub fn new(column: usize, kind: impl Into<ParseErrorKind>) -> Self {
+        Self {
+            column,
+            kind: kind.into(),
+        }
+    }
+
+    /// Error located at `part`, which has to be a subslice of `line`
+    pub fn at(line: &str, part: &str, kind: impl Into<ParseErrorKind>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len())
+            .unwrap_or(0);
+        Self::new(line[..offset].chars().count() + 1, kind)
+    }
+
+    /// Error located right after the end of `line`, e.g. if something is missing
+    pub fn after(line: &str, kind: impl Into<ParseErrorKind>) -> Self {
+        Self::new(line.chars().count() + 1, kind)
+    }
+
+    pub fn locate(self, day: u8, line: usize, text: &str) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column: self.column,
+            text: text.to_owned(),
+            kind: self.kind,
+        }
+    }
+}
+
+impl From<ParseErrorKind> for LineError {
+    fn from(kind: ParseErrorKind) -> Self {
+        Self::new(1, kind)
+    }
+}
+
+impl From<ParseIntError> for LineError {
+    fn from(e: ParseIntError) -> Self {
+        Self::new(1, e)
+    }
+}
+
+/// How line based parsers treat lines, which can't be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Stop at the first invalid line and return its error
+    FailFast,
+    /// Leave out invalid lines and report them next to the result
+    Skip,
+    /// Panic at the first invalid line
+    Panic,
+}
+
+/// Result of a parser together with the lines it skipped, in the order of the input
+#[derive(Debug)]
+pub struct Parsed<R> {
+    pub result: R,
+    pub skipped: Vec<ParseError>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_subslice() {
+        let line = "0,9 -> x,9";
+        let error = LineError::at(line, &line[7..8], ParseErrorKind::InvalidChar('x'));
+        assert_eq!(8, error.column);
+    }
+
+    #[test]
+    fn display_location() {
+        let error = LineError::new(3, ParseErrorKind::InvalidChar('2')).locate(3, 12, "012");
+        assert_eq!(
+            "day 3, line 12, column 3: invalid char '2' in '012'",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::{LineError, ParseError, ParseErrorKind};
+
+/// Position within a grid as (x, y), where (0, 0) is the top left corner
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS4: [(i64, i64); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS8: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangular grid, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /// Parses one cell per char, rejecting chars for which `f` returns None
+    pub fn parse_with(
+        day: u8,
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    LineError::new(column + 1, ParseErrorKind::InvalidChar(c)).locate(
+                        day,
+                        i + 1,
+                        line,
+                    )
+                })?;
+                cells.push(cell);
+            }
+            let row_len = cells.len() - row_start;
+            match width {
+                Some(width) if width != row_len => {
+                    return Err(LineError::new(
+                        width.min(row_len) + 1,
+                        ParseErrorKind::InvalidRowFormat,
+                    )
+                    .locate(day, i + 1, line))
+                }
+                _ => width = Some(row_len),
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            None => Err(LineError::new(1, ParseErrorKind::EmptyInput).locate(day, 1, "")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Bounds checked access, which allows to look past the edges with signed coordinates
+    pub fn get(&self, (x, y): (i64, i64)) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (i64, i64)) -> Option<&mut T> {
+        self.index_of(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, x: i64, y: i64) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+        Some(x + y * self.width)
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Horizontal and vertical neighbours within the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, &NEIGHBOURS4)
+    }
+
+    /// Horizontal, vertical and diagonal neighbours within the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, &NEIGHBOURS8)
+    }
+
+    fn neighbours(&self, (x, y): Pos, offsets: &'static [(i64, i64)]) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            ((0..width).contains(&nx) && (0..height).contains(&ny))
+                .then_some((nx as usize, ny as usize))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.into_iter().map(f).collect(),
+        }
+    }
+
+    /// Renders one char per cell and a newline between rows
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                out.push('\n');
+            }
+            out.extend(row.iter().map(&mut f));
+        }
+        out
+    }
+}
+
+impl Grid<u8> {
+    pub fn parse_digits(day: u8, input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(day, input, |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &Self::Output {
+        assert!(x < self.width, "x {} is out of bounds", x);
+        &self.cells[x + y * self.width]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut Self::Output {
+        assert!(x < self.width, "x {} is out of bounds", x);
+        &mut self.cells[x + y * self.width]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_digits() {
+        let grid = Grid::parse_digits(9, "123\n456").unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!("123\n456", grid.to_string());
+    }
+
+    #[test]
+    fn locate_invalid_digit() {
+        let error = Grid::parse_digits(9, "123\n1x3").unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+
+    #[test]
+    fn locate_inconsistent_width() {
+        let error = Grid::parse_digits(9, "123\n1234").unwrap_err();
+        assert_eq!((2, 4), (error.line, error.column));
+        let error = Grid::parse_digits(9, "123\n12").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
+
+    #[test]
+    fn signed_access() {
+        let grid = Grid::parse_digits(9, "12\n34").unwrap();
+        assert_eq!(Some(&4), grid.get((1, 1)));
+        assert_eq!(None, grid.get((-1, 0)));
+        assert_eq!(None, grid.get((2, 0)));
+    }
+
+    #[test]
+    fn neighbours_at_corner() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+        assert_eq!(3, grid.neighbours8((2, 2)).count());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(&[10, 11, 12], grid.row(1));
+        assert_eq!(vec![&1, &11], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            "#.#\n#.#",
+            grid.render(|v| if v % 2 == 0 { '#' } else { '.' })
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable to override the directory, in which puzzle inputs are looked up
+pub const ROOT_ENV: &str = "AOC_INPUT_DIR";
+const DEFAULT_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/puzzleData");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variant {
+    /// Personal puzzle input, stored as `day<N>.txt`
+    Real,
+    /// Example from the puzzle description, stored as `day<N>_test.txt`
+    Example,
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Real => f.write_str("real"),
+            Variant::Example => f.write_str("example"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum InputError {
+    #[error("No {variant} input for day {day}, expected it at {}", path.display())]
+    Missing {
+        day: u8,
+        variant: Variant,
+        path: PathBuf,
+    },
+    #[error("Cannot read {}: {source}", path.display())]
+    Unreadable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Directory of puzzle inputs, which are resolved by day and variant
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses the directory of `AOC_INPUT_DIR` or the crate's `puzzleData` otherwise
+    pub fn from_env() -> Self {
+        Self::new(std::env::var_os(ROOT_ENV).map_or_else(|| DEFAULT_ROOT.into(), PathBuf::from))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path(&self, day: u8, variant: Variant) -> PathBuf {
+        self.root.join(match variant {
+            Variant::Real => format!("day{}.txt", day),
+            Variant::Example => format!("day{}_test.txt", day),
+        })
+    }
+
+    pub fn read(&self, day: u8, variant: Variant) -> Result<String, InputError> {
+        let path = self.path(day, variant);
+        std::fs::read_to_string(&path).map_err(|source| {
+            if source.kind() == std::io::ErrorKind::NotFound {
+                InputError::Missing { day, variant, path }
+            } else {
+                InputError::Unreadable { path, source }
+            }
+        })
+    }
+
+    /// All inputs within the root, ordered by day and variant
+    pub fn discover(&self) -> Result<Vec<(u8, Variant)>, InputError> {
+        let entries = std::fs::read_dir(&self.root).map_err(|source| InputError::Unreadable {
+            path: self.root.clone(),
+            source,
+        })?;
+        let mut found = entries
+            .filter_map(|entry| parse_file_name(entry.ok()?.file_name().to_str()?))
+            .collect::<Vec<_>>();
+        found.sort_unstable();
+        Ok(found)
+    }
+}
+
+fn parse_file_name(name: &str) -> Option<(u8, Variant)> {
+    let stem = name.strip_prefix("day")?.strip_suffix(".txt")?;
+    match stem.strip_suffix("_test") {
+        Some(day) => Some((day.parse().ok()?, Variant::Example)),
+        None => Some((stem.parse().ok()?, Variant::Real)),
+    }
+}
+
+/// Reads an input from the store configured by the environment
+pub fn read_input(day: u8, variant: Variant) -> Result<String, InputError> {
+    InputStore::from_env().read(day, variant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discover_puzzle_data() {
+        let found = InputStore::new(DEFAULT_ROOT).discover().unwrap();
+        assert!(found.contains(&(1, Variant::Real)));
+        assert!(found.contains(&(8, Variant::Example)));
+        assert!(!found.contains(&(1, Variant::Example)));
+    }
+
+    #[test]
+    fn missing_input_names_path() {
+        let error = InputStore::new("does/not/exist")
+            .read(3, Variant::Example)
+            .unwrap_err();
+        assert_eq!(
+            "No example input for day 3, expected it at does/not/exist/day3_test.txt",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(Some((12, Variant::Real)), parse_file_name("day12.txt"));
+        assert_eq!(
+            Some((8, Variant::Example)),
+            parse_file_name("day8_test.txt")
+        );
+        assert_eq!(None, parse_file_name("notes.txt"));
+    }
+}
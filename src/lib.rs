@@ -1,20 +1,41 @@
 #![allow(dead_code)]
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+pub mod inputs;
+mod solution;
 mod utils;
+pub mod verify;
 
-type DynError = Box<dyn std::error::Error>;
+pub use error::{ErrorPolicy, ParseError, ParseErrorKind, Parsed};
+pub use grid::Grid;
+pub use inputs::{read_input, InputStore, Variant};
+pub use solution::{solution, solutions, Answer, InvalidPart, Part, Solution};
+pub use verify::{load_answers, verify, Report};
+
+pub type DynError = Box<dyn std::error::Error>;
@@ -0,0 +1,92 @@
+use std::{io::Read, time::Instant};
+
+use aoc2021::{load_answers, solution, verify, DynError, InputStore, Variant};
+
+const USAGE: &str = "Usage: aoc <day> [part] [input]
+       aoc verify [day]
+
+    day    Day of the puzzle (1-25)
+    part   1 or 2, runs both parts if omitted
+    input  Path to the puzzle input, '-' reads from stdin
+           'example' uses the example of the input directory
+           Defaults to the real input of the input directory
+
+verify checks all solutions, or those of a single day, against answers.txt
+
+The input directory is puzzleData or the one in AOC_INPUT_DIR";
+
+fn main() {
+    if let Err(e) = run(std::env::args().skip(1).collect()) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), DynError> {
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if args.len() > 3 {
+        return Err(format!("Too many arguments\n\n{}", USAGE).into());
+    }
+    if args[0] == "verify" {
+        return run_verify(args.get(1));
+    }
+    let day: u8 = args[0]
+        .parse()
+        .map_err(|_| format!("Invalid day '{}'", args[0]))?;
+    let solution = solution(day).ok_or_else(|| format!("Day {} is not solved yet", day))?;
+    let parts = match args.get(1) {
+        Some(part) => vec![part.parse()?],
+        None => solution.parts().to_vec(),
+    };
+    let input = read_input(day, args.get(2).map(String::as_str))?;
+
+    for part in parts {
+        if !solution.parts().contains(&part) {
+            println!("Day {} has no part {}", day, part);
+            continue;
+        }
+        let start = Instant::now();
+        let answer = solution.solve(part, &input)?;
+        let elapsed = start.elapsed();
+        println!("Day {} part {}: {} ({:?})", day, part, answer, elapsed);
+    }
+    Ok(())
+}
+
+fn run_verify(day: Option<&String>) -> Result<(), DynError> {
+    let day = day
+        .map(|day| {
+            day.parse::<u8>()
+                .map_err(|_| format!("Invalid day '{}'", day))
+        })
+        .transpose()?;
+    let store = InputStore::from_env();
+    let mut answers = load_answers(&store)?;
+    answers.retain(|a| day.is_none_or(|day| a.day == day));
+    let mut report = verify(&store, &answers);
+    report.checks.retain(|c| day.is_none_or(|day| c.day == day));
+    println!("{}", report);
+    if report.passed() {
+        Ok(())
+    } else {
+        Err("Verification failed".into())
+    }
+}
+
+fn read_input(day: u8, path: Option<&str>) -> Result<String, DynError> {
+    match path {
+        Some("-") => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+        Some("example") => Ok(InputStore::from_env().read(day, Variant::Example)?),
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path, e).into())
+        }
+        None => Ok(InputStore::from_env().read(day, Variant::Real)?),
+    }
+}
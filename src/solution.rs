@@ -0,0 +1,134 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9, DynError,
+};
+
+pub type Answer = Box<dyn Display>;
+
+/// A puzzle of a single day, which can solve both parts for any input
+pub trait Solution {
+    /// Parses the input without solving it, so parsing can be timed on its own.
+    /// Days which parse and solve in a single pass keep this default.
+    fn parse(&self, _input: &str) -> Result<(), DynError> {
+        Ok(())
+    }
+    fn part1(&self, input: &str) -> Result<Answer, DynError>;
+    fn part2(&self, input: &str) -> Result<Answer, DynError>;
+
+    /// Parts of the puzzle, where the last day of the calendar only has the first one
+    fn parts(&self) -> &'static [Part] {
+        &Part::ALL
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, DynError> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Invalid part '{0}', expected 1 or 2")]
+pub struct InvalidPart(String);
+
+impl FromStr for Part {
+    type Err = InvalidPart;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(InvalidPart(s.to_owned())),
+        }
+    }
+}
+
+const REGISTRY: &[(u8, &dyn Solution)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+    (11, &day11::Day11),
+    (12, &day12::Day12),
+    (13, &day13::Day13),
+    (14, &day14::Day14),
+    (15, &day15::Day15),
+    (16, &day16::Day16),
+    (17, &day17::Day17),
+    (18, &day18::Day18),
+    (19, &day19::Day19),
+    (20, &day20::Day20),
+    (21, &day21::Day21),
+    (22, &day22::Day22),
+    (23, &day23::Day23),
+    (24, &day24::Day24),
+    (25, &day25::Day25),
+];
+
+/// All registered solutions, ordered by day
+pub fn solutions() -> impl Iterator<Item = (u8, &'static dyn Solution)> {
+    REGISTRY.iter().copied()
+}
+
+pub fn solution(day: u8) -> Option<&'static dyn Solution> {
+    solutions().find(|(d, _)| *d == day).map(|(_, s)| s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{read_input, Variant};
+
+    #[test]
+    fn registry_is_ordered_by_day() {
+        let days = solutions().map(|(day, _)| day).collect::<Vec<_>>();
+        assert_eq!((1..=25).collect::<Vec<_>>(), days);
+    }
+
+    #[test]
+    fn solve_by_day() {
+        let input = read_input(1, Variant::Real).unwrap();
+        let day1 = solution(1).unwrap();
+        assert_eq!("1688", day1.solve(Part::One, &input).unwrap().to_string());
+        assert_eq!("1728", day1.solve(Part::Two, &input).unwrap().to_string());
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert!(solution(26).is_none());
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!(Part::Two, "2".parse().unwrap());
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -1,7 +1,145 @@
-#[derive(thiserror::Error, Debug)]
-pub enum AocError {
-    #[error("Empty input")]
-    EmptyInput,
-    #[error("InvalidRowFormat")]
-    InvalidRowFormat,
+use std::{io::Read, num::ParseIntError, rc::Rc, str::FromStr};
+
+use simple_lines::ReadExt;
+
+use crate::error::{ErrorPolicy, LineError, ParseError, ParseErrorKind, Parsed};
+
+/// Lines of the reader with their 1-based line number
+pub fn numbered_lines(
+    day: u8,
+    r: impl Read,
+) -> impl Iterator<Item = Result<(usize, Rc<String>), ParseError>> {
+    r.lines_rc().enumerate().map(move |(i, line)| {
+        line.map(|line| (i + 1, line)).map_err(|e| {
+            LineError::new(1, ParseErrorKind::Unreadable(e.to_string())).locate(day, i + 1, "")
+        })
+    })
+}
+
+/// Parses every line on its own, locating errors of `f` within the input
+pub fn parse_lines<T>(
+    day: u8,
+    r: impl Read,
+    mut f: impl FnMut(&str) -> Result<T, LineError>,
+) -> impl Iterator<Item = Result<T, ParseError>> {
+    numbered_lines(day, r).map(move |line| {
+        let (no, line) = line?;
+        f(&line).map_err(|e| e.locate(day, no, &line))
+    })
+}
+
+/// Parses every line on its own like `parse_lines` and hands the values to `consume`,
+/// while invalid lines are treated according to the `policy`
+pub fn parse_lines_with<T, R>(
+    day: u8,
+    r: impl Read,
+    policy: ErrorPolicy,
+    f: impl FnMut(&str) -> Result<T, LineError>,
+    consume: impl FnOnce(&mut dyn Iterator<Item = T>) -> R,
+) -> Result<Parsed<R>, ParseError> {
+    let mut skipped = Vec::new();
+    let mut error = None;
+    let mut values = parse_lines(day, r, f)
+        .map_while(|value| match value {
+            Ok(value) => Some(Some(value)),
+            Err(e) => match policy {
+                ErrorPolicy::FailFast => {
+                    error = Some(e);
+                    None
+                }
+                ErrorPolicy::Skip => {
+                    skipped.push(e);
+                    Some(None)
+                }
+                ErrorPolicy::Panic => panic!("{}", e),
+            },
+        })
+        .flatten();
+    let result = consume(&mut values);
+    drop(values);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(Parsed { result, skipped }),
+    }
+}
+
+/// Parses a single line of numbers, which are separated by `separator`
+pub fn parse_number_list<T: FromStr<Err = ParseIntError>>(
+    day: u8,
+    line: &str,
+    separator: char,
+) -> Result<Vec<T>, ParseError> {
+    line.split(separator)
+        .map(|no| {
+            no.parse()
+                .map_err(|e| LineError::at(line, no, e).locate(day, 1, line))
+        })
+        .collect()
+}
+
+/// Runs `step` until it returns true and returns the number of that step, starting at 1
+pub fn first_step(mut step: impl FnMut() -> bool) -> usize {
+    (1..).find(|_| step()).unwrap()
+}
+
+/// Deterministic pseudo random numbers below the given bound for test fixtures
+#[cfg(test)]
+pub fn pseudo_random(seed: u64) -> impl FnMut(u64) -> u64 {
+    let mut state = seed;
+    move |bound| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = b"1\nx\n3\n4y";
+
+    fn sum(policy: ErrorPolicy) -> Result<Parsed<u32>, ParseError> {
+        parse_lines_with(
+            0,
+            INPUT,
+            policy,
+            |line| Ok(line.parse::<u32>()?),
+            |values| values.sum(),
+        )
+    }
+
+    #[test]
+    fn skip_invalid_lines() {
+        let parsed = sum(ErrorPolicy::Skip).unwrap();
+        assert_eq!(4, parsed.result);
+        let skipped = parsed
+            .skipped
+            .iter()
+            .map(|e| (e.line, e.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(2, "x"), (4, "4y")], skipped);
+    }
+
+    #[test]
+    fn fail_fast() {
+        assert_eq!(2, sum(ErrorPolicy::FailFast).unwrap_err().line);
+        let parsed = parse_lines_with(
+            0,
+            &b"1\n2"[..],
+            ErrorPolicy::FailFast,
+            |line| Ok(line.parse::<u32>()?),
+            |values| values.count(),
+        )
+        .unwrap();
+        assert_eq!(2, parsed.result);
+        assert!(parsed.skipped.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2")]
+    fn panic_at_invalid_line() {
+        let _ = sum(ErrorPolicy::Panic);
+    }
 }
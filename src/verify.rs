@@ -0,0 +1,307 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{LineError, ParseErrorKind},
+    inputs::{InputError, InputStore, Variant},
+    solution::{solution, solutions, Part},
+};
+
+/// File within the input directory, which stores one `<day> <part> <variant> <answer>` per line
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(thiserror::Error, Debug)]
+pub enum AnswersError {
+    #[error(transparent)]
+    Input(#[from] InputError),
+    #[error("{ANSWERS_FILE}, line {line}, column {column}: {kind} in '{text}'")]
+    Invalid {
+        line: usize,
+        column: usize,
+        text: String,
+        kind: ParseErrorKind,
+    },
+}
+
+/// Known answer of a single part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    pub variant: Variant,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    Error(String),
+    /// Registered solution without a known answer for its real input
+    NoAnswer,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub variant: Variant,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+/// Outcome of every check, which is displayed as a table
+#[derive(Debug)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// True if no answer was wrong or failed, missing answers don't count
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|c| matches!(c.outcome, Outcome::Pass | Outcome::NoAnswer))
+    }
+
+    fn count(&self, f: impl Fn(&Outcome) -> bool) -> usize {
+        self.checks.iter().filter(|c| f(&c.outcome)).count()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>3} {:>4} {:<7} {:<6} {:>10}",
+            "Day", "Part", "Variant", "Result", "Time"
+        )?;
+        for check in &self.checks {
+            let (result, details) = match &check.outcome {
+                Outcome::Pass => ("pass", String::new()),
+                Outcome::Fail { expected, actual } => (
+                    "FAIL",
+                    format!("expected '{}', got '{}'", escape(expected), escape(actual)),
+                ),
+                Outcome::Error(e) => ("ERROR", e.clone()),
+                Outcome::NoAnswer => ("-", "no answer".to_owned()),
+            };
+            write!(
+                f,
+                "{:>3} {:>4} {:<7} {:<6} {:>10}",
+                check.day,
+                check.part,
+                check.variant,
+                result,
+                format!("{:.1?}", check.elapsed),
+            )?;
+            if details.is_empty() {
+                writeln!(f)?;
+            } else {
+                writeln!(f, " {}", details)?;
+            }
+        }
+        write!(
+            f,
+            "{} passed, {} failed, {} without answer in {:.1?}",
+            self.count(|o| *o == Outcome::Pass),
+            self.count(|o| matches!(o, Outcome::Fail { .. } | Outcome::Error(_))),
+            self.count(|o| *o == Outcome::NoAnswer),
+            self.checks.iter().map(|c| c.elapsed).sum::<Duration>()
+        )
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\n', "\\n")
+}
+
+/// Parses the answers file, where empty lines and lines starting with '#' are skipped
+pub fn parse_answers(input: &str) -> Result<Vec<Expected>, AnswersError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            parse_answer(line).map_err(|e| AnswersError::Invalid {
+                line: i + 1,
+                column: e.column,
+                text: line.to_owned(),
+                kind: e.kind,
+            })
+        })
+        .collect()
+}
+
+fn parse_answer(line: &str) -> Result<Expected, LineError> {
+    let mut fields = line.splitn(4, ' ');
+    let mut next = |name| {
+        fields
+            .next()
+            .ok_or_else(|| LineError::after(line, ParseErrorKind::Missing(name)))
+    };
+    let day = next("day")?;
+    let day = day.parse().map_err(|e| LineError::at(line, day, e))?;
+    let part = next("part")?;
+    let part = part
+        .parse()
+        .map_err(|_| LineError::at(line, part, ParseErrorKind::Invalid("part")))?;
+    let variant = match next("variant")? {
+        "real" => Variant::Real,
+        "example" => Variant::Example,
+        other => {
+            return Err(LineError::at(
+                line,
+                other,
+                ParseErrorKind::Invalid("variant"),
+            ))
+        }
+    };
+    let answer = next("answer")?.replace("\\n", "\n");
+    Ok(Expected {
+        day,
+        part,
+        variant,
+        answer,
+    })
+}
+
+/// Reads the answers file of the store
+pub fn load_answers(store: &InputStore) -> Result<Vec<Expected>, AnswersError> {
+    let path = store.root().join(ANSWERS_FILE);
+    let content =
+        std::fs::read_to_string(&path).map_err(|source| InputError::Unreadable { path, source })?;
+    parse_answers(&content)
+}
+
+/// Runs the solution of every answer and reports registered parts without a real answer
+pub fn verify(store: &InputStore, answers: &[Expected]) -> Report {
+    let mut checks = answers
+        .iter()
+        .map(|expected| check(store, expected))
+        .collect::<Vec<_>>();
+    for (day, solution) in solutions() {
+        for &part in solution.parts() {
+            let answered = answers
+                .iter()
+                .any(|a| (a.day, a.part, a.variant) == (day, part, Variant::Real));
+            if !answered {
+                checks.push(Check {
+                    day,
+                    part,
+                    variant: Variant::Real,
+                    outcome: Outcome::NoAnswer,
+                    elapsed: Duration::ZERO,
+                });
+            }
+        }
+    }
+    checks.sort_by_key(|c| (c.day, c.variant, c.part));
+    Report { checks }
+}
+
+fn check(store: &InputStore, expected: &Expected) -> Check {
+    let start = Instant::now();
+    let outcome = match (
+        solution(expected.day),
+        store.read(expected.day, expected.variant),
+    ) {
+        (None, _) => Outcome::Error(format!("Day {} is not solved yet", expected.day)),
+        (_, Err(e)) => Outcome::Error(e.to_string()),
+        (Some(solution), Ok(input)) => match solution.solve(expected.part, &input) {
+            Ok(answer) if answer.to_string() == expected.answer => Outcome::Pass,
+            Ok(answer) => Outcome::Fail {
+                expected: expected.answer.clone(),
+                actual: answer.to_string(),
+            },
+            Err(e) => Outcome::Error(e.to_string()),
+        },
+    };
+    Check {
+        day: expected.day,
+        part: expected.part,
+        variant: expected.variant,
+        outcome,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers_file() {
+        let answers = parse_answers("# comment\n\n8 1 example 26\n13 2 real #.\\n.#").unwrap();
+        assert_eq!(2, answers.len());
+        assert_eq!(
+            (8, Part::One, Variant::Example),
+            (answers[0].day, answers[0].part, answers[0].variant)
+        );
+        assert_eq!("#.\n.#", answers[1].answer);
+    }
+
+    #[test]
+    fn locate_invalid_answer() {
+        match parse_answers("1 1 real 1688\n1 3 real 1728").unwrap_err() {
+            AnswersError::Invalid { line, column, .. } => assert_eq!((2, 3), (line, column)),
+            e => panic!("Unexpected error {}", e),
+        }
+        assert!(parse_answers("1 1 test 1688").is_err());
+        assert!(parse_answers("1 1 real").is_err());
+    }
+
+    #[test]
+    fn verify_example() {
+        let store = InputStore::from_env();
+        let answers = parse_answers("8 1 example 26\n8 2 example 1").unwrap();
+        let report = verify(&store, &answers);
+        assert!(!report.passed());
+        let outcomes = report
+            .checks
+            .iter()
+            .filter(|c| c.day == 8)
+            .map(|c| (c.part, c.variant, c.outcome.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Part::One, Variant::Real, Outcome::NoAnswer),
+                (Part::Two, Variant::Real, Outcome::NoAnswer),
+                (Part::One, Variant::Example, Outcome::Pass),
+                (
+                    Part::Two,
+                    Variant::Example,
+                    Outcome::Fail {
+                        expected: "1".to_owned(),
+                        actual: "61229".to_owned()
+                    }
+                ),
+            ],
+            outcomes
+        );
+    }
+
+    #[test]
+    fn every_solution_has_an_answer() {
+        let store = InputStore::from_env();
+        let answers = load_answers(&store).unwrap();
+        let inputs = store.discover().unwrap();
+        for (day, solution) in
+            solutions().filter(|(day, _)| inputs.contains(&(*day, Variant::Real)))
+        {
+            for &part in solution.parts() {
+                assert!(
+                    answers.iter().any(|a| (a.day, a.part) == (day, part)),
+                    "No answer for day {} part {}",
+                    day,
+                    part
+                );
+            }
+        }
+    }
+}